@@ -0,0 +1,468 @@
+//! Spectrum analysis for metering.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    fmt::Debug,
+    marker::PhantomData,
+};
+
+use crate::{
+    fft::Stft,
+    frame::Frame,
+    node::{
+        Node,
+        CHUNK,
+    },
+    num::{
+        Complex,
+        Float,
+        Fp,
+        Real,
+    },
+    sync::{
+        triple_in,
+        TripleReader,
+        TripleWriter,
+    },
+    window::Window,
+    Buf,
+};
+
+/// Frequency bins of a published spectrum.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Binning {
+    /// Bins of the transform, from DC to Nyquist.
+    #[default]
+    Linear,
+    /// `bands` bins spaced logarithmically from `min_freq`, as a fraction of
+    /// the sample rate, to Nyquist.  Each bin holds the largest magnitude
+    /// within its band, or the interpolated magnitude at its center if the
+    /// band is narrower than the bins of the transform.
+    Log { bands: usize, min_freq: Fp },
+}
+
+/// Averaging of successive spectra.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Averaging {
+    /// Latest spectrum only.
+    #[default]
+    Off,
+    /// Hold peaks, multiplied by `decay` at every analysis frame.
+    PeakHold { decay: Fp },
+    /// Exponential moving average: the previous value is weighted by
+    /// `decay`, the new one by `1 - decay`.
+    Exponential { decay: Fp },
+}
+
+impl Averaging {
+    fn apply(
+        self,
+        state: &mut Fp,
+        value: Fp,
+    ) {
+        *state = match self {
+            Self::Off => value,
+            Self::PeakHold {
+                decay,
+            } => value.max(*state * decay),
+            Self::Exponential {
+                decay,
+            } => value + decay * (*state - value),
+        };
+    }
+}
+
+/// Spectrum published by an [`Analyzer`].
+#[derive(Debug)]
+pub struct Spectrum<A = Global>
+where
+    A: Allocator,
+{
+    magnitudes: Buf<Fp, A>,
+    freqs:      Buf<Fp, A>,
+    count:      u64,
+}
+
+impl<A> Spectrum<A>
+where
+    A: Allocator,
+{
+    /// Magnitudes of the bins, as linear amplitudes: a full scale sine
+    /// reads 1.
+    pub fn magnitudes(&self) -> &[Fp] {
+        self.magnitudes.as_slice()
+    }
+
+    /// Center frequencies of the bins, as fractions of the sample rate.
+    pub fn freqs(&self) -> &[Fp] {
+        self.freqs.as_slice()
+    }
+
+    /// Number of bins.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.magnitudes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.magnitudes.is_empty()
+    }
+
+    /// Number of analysis frames so far.  Zero before the first one is
+    /// published.  It never decreases, not even on reset.
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+}
+
+// Log band covering bins `lo..hi` of the transform, or interpolated at
+// `pos` if empty.
+#[derive(Debug, Clone, Copy, Default)]
+struct Band {
+    lo:  usize,
+    hi:  usize,
+    pos: Fp,
+}
+
+// Everything after the transform, apart from the `Stft` it is called by.
+struct Meter<A>
+where
+    A: Allocator,
+{
+    // amplitude of a bin, from the sum of the window
+    scale:  Fp,
+    bands:  Buf<Band, A>,
+    mags:   Buf<Fp, A>,
+    state:  Buf<Fp, A>,
+    writer: TripleWriter<Spectrum<A>, A>,
+    count:  u64,
+}
+
+impl<A> Meter<A>
+where
+    A: Allocator,
+{
+    fn update(
+        &mut self,
+        spectrum: &[Complex],
+        averaging: Averaging,
+    ) {
+        let last = spectrum.len() - 1;
+        for (k, (y, x)) in self.mags.iter_mut().zip(spectrum).enumerate() {
+            // DC and Nyquist are not mirrored
+            let scale = if k == 0 || k == last {
+                self.scale * 0.5
+            } else {
+                self.scale
+            };
+            *y = x.norm() * scale;
+        }
+
+        if self.bands.is_empty() {
+            for (s, x) in self.state.iter_mut().zip(&self.mags) {
+                averaging.apply(s, *x);
+            }
+        } else {
+            let mags = self.mags.as_slice();
+            for (s, band) in self.state.iter_mut().zip(&self.bands) {
+                let value = if band.lo < band.hi {
+                    mags[band.lo..band.hi].iter().fold(0., |m, x| x.max(m))
+                } else {
+                    #[allow(
+                        clippy::cast_possible_truncation,
+                        clippy::cast_sign_loss,
+                        clippy::cast_precision_loss
+                    )]
+                    let (k, t) = {
+                        let k = (band.pos as usize).min(last - 1);
+                        (k, band.pos - k as Fp)
+                    };
+                    mags[k] + t * (mags[k + 1] - mags[k])
+                };
+                averaging.apply(s, value);
+            }
+        }
+
+        self.count += 1;
+        let out = self.writer.write();
+        out.magnitudes
+            .as_mut_slice()
+            .copy_from_slice(self.state.as_slice());
+        out.count = self.count;
+        self.writer.publish();
+    }
+}
+
+/// Pass-through node publishing the spectrum of its input.
+///
+/// Channels are mixed to mono and analyzed by a short-time Fourier
+/// transform.  After every analysis frame, the magnitudes are binned,
+/// averaged, and published through a lock-free triple buffer, so that
+/// another thread can read the latest [`Spectrum`] from the
+/// [`TripleReader`] returned on construction, without ever blocking the
+/// audio thread.  Nothing is allocated after construction.
+pub struct Analyzer<T, A = Global>
+where
+    A: Allocator,
+{
+    /// Averaging of successive spectra.  Changing it takes effect at the
+    /// next analysis frame.
+    pub averaging: Averaging,
+    binning:       Binning,
+    stft:          Stft<A>,
+    meter:         Meter<A>,
+    _marker:       PhantomData<T>,
+}
+
+impl<T, A> Analyzer<T, A>
+where
+    A: Allocator + Clone,
+    T: Frame,
+{
+    /// Analyzer with frames of `size` samples taken every `hop` samples,
+    /// and the reader of its spectra.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 4, if `hop` is
+    /// zero or greater than `size`, or, for [`Binning::Log`], if `bands` is
+    /// zero or `min_freq` is not in `(0, 0.5)`.
+    pub fn new_in(
+        size: usize,
+        hop: usize,
+        window: Window,
+        binning: Binning,
+        averaging: Averaging,
+        alloc: A,
+    ) -> (Self, TripleReader<Spectrum<A>, A>) {
+        assert!(size >= 4, "size must be at least 4");
+        let stft = Stft::new_in(size, hop, window, alloc.clone());
+        let bins = stft.bins();
+        let scale = 2. / stft.window().iter().sum::<Fp>();
+
+        let len = match binning {
+            Binning::Linear => bins,
+            Binning::Log {
+                bands,
+                min_freq,
+            } => {
+                assert!(bands > 0, "number of bands must not be zero");
+                assert!(
+                    min_freq > 0. && min_freq < 0.5,
+                    "min_freq must be in (0, 0.5)"
+                );
+                bands
+            }
+        };
+        let mut bands = Buf::alloc_new_in(
+            if binning == Binning::Linear { 0 } else { len },
+            alloc.clone(),
+        );
+        if let Binning::Log {
+            min_freq, ..
+        } = binning
+        {
+            log_bands(min_freq, size, bands.as_mut_slice());
+        }
+
+        let (writer, reader) = triple_in(
+            || {
+                let mut freqs = Buf::alloc_new_in(len, alloc.clone());
+                centers(binning, size, freqs.as_mut_slice());
+                Spectrum {
+                    magnitudes: Buf::alloc_new_in(len, alloc.clone()),
+                    freqs,
+                    count: 0,
+                }
+            },
+            alloc.clone(),
+        );
+        let analyzer = Self {
+            averaging,
+            binning,
+            stft,
+            meter: Meter {
+                scale,
+                bands,
+                mags: Buf::alloc_new_in(bins, alloc.clone()),
+                state: Buf::alloc_new_in(len, alloc),
+                writer,
+                count: 0,
+            },
+            _marker: PhantomData,
+        };
+        (analyzer, reader)
+    }
+}
+
+impl<T, A> Analyzer<T, A>
+where
+    A: Allocator,
+{
+    /// Number of samples in an analysis frame.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.stft.size()
+    }
+
+    /// Number of samples between analysis frames.
+    #[must_use]
+    pub fn hop(&self) -> usize {
+        self.stft.hop()
+    }
+
+    #[must_use]
+    pub fn binning(&self) -> Binning {
+        self.binning
+    }
+
+    /// Number of bins of the published spectrum.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.meter.state.len()
+    }
+
+    /// Always false: there is at least one bin.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.meter.state.is_empty()
+    }
+}
+
+impl<T> Analyzer<T>
+where
+    T: Frame,
+{
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// See [`new_in`](Self::new_in).
+    #[must_use]
+    pub fn new(
+        size: usize,
+        hop: usize,
+        window: Window,
+        binning: Binning,
+        averaging: Averaging,
+    ) -> (Self, TripleReader<Spectrum>) {
+        Self::new_in(size, hop, window, binning, averaging, Global)
+    }
+}
+
+// Edges of log band `i` of `bands.len()`, from `min_freq` to Nyquist.
+#[allow(clippy::cast_precision_loss)]
+fn log_edge(
+    min_freq: Fp,
+    i: usize,
+    bands: usize,
+) -> Fp {
+    min_freq * <Fp as Real>::powf(0.5 / min_freq, i as Fp / bands as Fp)
+}
+
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn log_bands(
+    min_freq: Fp,
+    size: usize,
+    bands: &mut [Band],
+) {
+    let len = bands.len();
+    let bin = |freq: Fp| freq * size as Fp;
+    for (i, band) in bands.iter_mut().enumerate() {
+        let lo = bin(log_edge(min_freq, i, len));
+        let hi = bin(log_edge(min_freq, i + 1, len));
+        *band = Band {
+            lo:  lo.ceil() as usize,
+            // the last band includes Nyquist
+            hi:  if i + 1 == len {
+                size / 2 + 1
+            } else {
+                hi.ceil() as usize
+            },
+            pos: (lo * hi).sqrt(),
+        };
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn centers(
+    binning: Binning,
+    size: usize,
+    freqs: &mut [Fp],
+) {
+    let len = freqs.len();
+    for (i, freq) in freqs.iter_mut().enumerate() {
+        *freq = match binning {
+            Binning::Linear => i as Fp / size as Fp,
+            Binning::Log {
+                min_freq, ..
+            } => (log_edge(min_freq, i, len) * log_edge(min_freq, i + 1, len))
+                .sqrt(),
+        };
+    }
+}
+
+impl<T, A> Node for Analyzer<T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        #[allow(clippy::cast_precision_loss)]
+        let norm = (T::zero().as_slice().len() as Fp).recip();
+        let averaging = self.averaging;
+        let mut buf: [Fp; CHUNK] = [0.; CHUNK];
+        for chunk in frames.chunks(CHUNK) {
+            let mono = &mut buf[..chunk.len()];
+            for (x, frm) in mono.iter_mut().zip(chunk) {
+                *x = frm
+                    .as_slice()
+                    .iter()
+                    .map(|s| s.to_float::<Fp>())
+                    .sum::<Fp>()
+                    * norm;
+            }
+            self.stft.analyze(mono, |spectrum| {
+                self.meter.update(spectrum, averaging);
+            });
+        }
+    }
+
+    /// Clear collected samples and averages.  Spectra already published
+    /// are kept, and so is the count of analysis frames.
+    fn reset_state(&mut self) {
+        self.stft.reset();
+        self.meter.state.zero();
+    }
+}
+
+impl<T, A> Debug for Analyzer<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Analyzer")
+            .field("averaging", &self.averaging)
+            .field("binning", &self.binning)
+            .field("size", &self.size())
+            .field("hop", &self.hop())
+            .finish_non_exhaustive()
+    }
+}
@@ -17,8 +17,29 @@ use std::{
 };
 
 use crate::{
+    control::{
+        Reset,
+        Visit,
+    },
     frame::Frame,
-    node::Node,
+    node::{
+        Node,
+        CHUNK,
+    },
+    num::{
+        zero,
+        Float,
+        Fp,
+        Real,
+    },
+};
+
+mod remote;
+
+pub use remote::{
+    Edit,
+    Editor,
+    Remote,
 };
 
 pub struct Bus<'a, T, A = Global>
@@ -26,6 +47,58 @@ where
     A: Allocator,
 {
     nodes: Vec<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>, A>,
+    // allocated on the first crossfade, or when reserving room
+    fades: Option<Vec<Fade<'a, T, A>, A>>,
+}
+
+// Node being faded out at `index`.  Once the fade is finished or
+// cancelled, `index` is `None` and the node waits to be taken with
+// `take_retired`, so that it is not dropped while processing.
+struct Fade<'a, T, A>
+where
+    A: Allocator,
+{
+    index: Option<usize>,
+    old:   Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    pos:   usize,
+    len:   usize,
+}
+
+impl<'a, T, A> Fade<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    fn is_done(&self) -> bool {
+        self.pos >= self.len
+    }
+
+    // Run the old and the new node side by side with an equal-power
+    // crossfade, then the new node alone.
+    #[allow(clippy::cast_precision_loss)]
+    fn proc(
+        &mut self,
+        node: &mut (dyn Node<Frame = T> + Sync + Send + 'a),
+        frames: &mut [T],
+    ) {
+        let split = frames.len().min(self.len - self.pos);
+        let (head, tail) = frames.split_at_mut(split);
+
+        let mut buf = [zero::<T>(); CHUNK];
+        for chunk in head.chunks_mut(CHUNK) {
+            let old = &mut buf[..chunk.len()];
+            old.copy_from_slice(chunk);
+            self.old.proc(old);
+            node.proc(chunk);
+
+            for (frm, x) in chunk.iter_mut().zip(old.iter()) {
+                let t = self.pos as Fp / self.len as Fp * Fp::PI * 0.5;
+                *frm = *frm * t.sin().to_float() + *x * t.cos().to_float();
+                self.pos += 1;
+            }
+        }
+        node.proc(tail);
+    }
 }
 
 impl<'a, T, A> Bus<'a, T, A>
@@ -35,6 +108,7 @@ where
     pub fn new_in(alloc: A) -> Self {
         Self {
             nodes: Vec::new_in(alloc),
+            fades: None,
         }
     }
 
@@ -48,6 +122,30 @@ where
         self.nodes.is_empty()
     }
 
+    /// Number of nodes the bus can hold without reallocating.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.nodes.capacity()
+    }
+
+    /// Reserve room for at least `additional` more nodes, and for as many
+    /// crossfades as nodes.
+    ///
+    /// Crossfaded nodes are kept until taken with
+    /// [`take_retired`](Self::take_retired): if they are not, crossfading
+    /// may allocate again.
+    pub fn reserve(
+        &mut self,
+        additional: usize,
+    ) where
+        A: Clone,
+    {
+        self.nodes.reserve(additional);
+        let capacity = self.nodes.capacity();
+        let fades = self.fades_mut();
+        fades.reserve(capacity.saturating_sub(fades.len()));
+    }
+
     pub fn push(
         &mut self,
         node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
@@ -58,7 +156,9 @@ where
     pub fn pop(
         &mut self
     ) -> Option<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>> {
-        self.nodes.pop()
+        let node = self.nodes.pop()?;
+        self.cancel_fade(self.nodes.len());
+        Some(node)
     }
 
     /// # Panics
@@ -70,6 +170,11 @@ where
         node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
     ) {
         self.nodes.insert(index, node);
+        for fade in self.fades.iter_mut().flatten() {
+            if let Some(i) = fade.index.as_mut().filter(|i| **i >= index) {
+                *i += 1;
+            }
+        }
     }
 
     /// # Panics
@@ -79,9 +184,21 @@ where
         &mut self,
         index: usize,
     ) -> Box<dyn Node<Frame = T> + Sync + Send + 'a, A> {
-        self.nodes.remove(index)
+        let node = self.nodes.remove(index);
+        self.cancel_fade(index);
+        for fade in self.fades.iter_mut().flatten() {
+            if let Some(i) = fade.index.as_mut().filter(|i| **i > index) {
+                *i -= 1;
+            }
+        }
+        node
     }
 
+    /// Replace the node instantly.
+    ///
+    /// A crossfade in progress at `index` is cancelled, and the node that
+    /// was being faded out is retired.
+    ///
     /// # Panics
     ///
     /// Panics if index is out of bounds.
@@ -90,7 +207,77 @@ where
         index: usize,
         node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
     ) -> Box<dyn Node<Frame = T> + Sync + Send + 'a, A> {
-        mem::replace(&mut self.nodes[index], node)
+        let old = mem::replace(&mut self.nodes[index], node);
+        self.cancel_fade(index);
+        old
+    }
+
+    /// Replace the node with an equal-power crossfade.
+    ///
+    /// The old and the new node run side by side for the next `frames`
+    /// frames processed by the bus.  Then the old node is retired.  If a
+    /// crossfade is already in progress at `index`, the node that was being
+    /// faded out is retired immediately.
+    ///
+    /// Retired nodes are not dropped by the bus: take them with
+    /// [`take_retired`](Self::take_retired).
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    pub fn replace_crossfade(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+        frames: usize,
+    ) where
+        A: Clone,
+    {
+        let old = self.replace(index, node);
+        self.fades_mut().push(Fade {
+            index: (frames > 0).then_some(index),
+            old,
+            pos: 0,
+            len: frames,
+        });
+    }
+
+    /// Take a node that has finished fading out, or whose crossfade has
+    /// been cancelled, to drop it outside of the audio thread.
+    pub fn take_retired(
+        &mut self
+    ) -> Option<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>> {
+        let fades = self.fades.as_mut()?;
+        let pos = fades.iter().position(|f| f.index.is_none())?;
+        Some(fades.swap_remove(pos).old)
+    }
+
+    /// Check if a crossfade is in progress at `index`.
+    #[must_use]
+    pub fn is_fading(
+        &self,
+        index: usize,
+    ) -> bool {
+        self.fades.iter().flatten().any(|f| f.index == Some(index))
+    }
+
+    fn cancel_fade(
+        &mut self,
+        index: usize,
+    ) {
+        for fade in self.fades.iter_mut().flatten() {
+            if fade.index == Some(index) {
+                fade.index = None;
+            }
+        }
+    }
+
+    fn fades_mut(&mut self) -> &mut Vec<Fade<'a, T, A>, A>
+    where
+        A: Clone,
+    {
+        let alloc = self.nodes.allocator();
+        self.fades.get_or_insert_with(|| Vec::new_in(alloc.clone()))
     }
 
     pub fn node_push_in<N>(
@@ -125,6 +312,7 @@ where
 
     pub fn clear(&mut self) {
         self.nodes.clear();
+        self.fades = None;
     }
 
     pub fn as_slice(
@@ -226,6 +414,7 @@ where
     ) -> std::fmt::Result {
         f.debug_struct("Bus")
             .field("nodes", &format_args!("Vec<Box<dyn Node>>"))
+            .field("fades", &self.fades.iter().flatten().count())
             .finish()
     }
 }
@@ -241,17 +430,80 @@ where
         &mut self,
         frames: &mut [Self::Frame],
     ) {
+        for (index, node) in self.nodes.iter_mut().enumerate() {
+            let fade = self
+                .fades
+                .iter_mut()
+                .flatten()
+                .find(|f| f.index == Some(index));
+            match fade {
+                Some(fade) => {
+                    fade.proc(node.as_mut(), frames);
+                    if fade.is_done() {
+                        fade.index = None;
+                    }
+                }
+                None => node.proc(frames),
+            }
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        for node in &mut self.nodes {
+            node.prepare(max_block, sample_rate);
+        }
+        for fade in self.fades.iter_mut().flatten() {
+            fade.old.prepare(max_block, sample_rate);
+        }
+    }
+
+    /// Sum of latencies of all nodes.
+    fn latency(&self) -> usize {
+        self.nodes.iter().map(|n| n.latency()).sum()
+    }
+
+    fn reset_state(&mut self) {
         for node in &mut self.nodes {
-            node.proc(frames);
+            node.reset_state();
+        }
+        for fade in self.fades.iter_mut().flatten() {
+            fade.old.reset_state();
         }
     }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            visitor.enter(i);
+            node.visit_params(visitor);
+            visitor.leave();
+        }
+    }
+}
+
+/// Reset the state of all nodes.
+impl<'a, T, A> Reset for Bus<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    fn reset(&mut self) {
+        self.reset_state();
+    }
 }
 
 impl<'a, T> Bus<'a, T> {
     #[must_use]
     pub fn new() -> Self {
         Self {
-            nodes: Vec::new()
+            nodes: Vec::new(),
+            fades: None,
         }
     }
 
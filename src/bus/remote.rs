@@ -0,0 +1,358 @@
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    fmt::Debug,
+};
+
+use super::Bus;
+use crate::{
+    control::Visit,
+    frame::Frame,
+    node::Node,
+    num::Fp,
+    sync::{
+        spsc_in,
+        Receiver,
+        Sender,
+    },
+};
+
+/// Structural edit of a [`Bus`] sent from another thread.
+///
+/// Edits with an index out of bounds, or adding a node to a bus at full
+/// capacity, are ignored: the node they carry is returned to the
+/// [`Editor`].
+pub enum Edit<'a, T, A = Global>
+where
+    A: Allocator,
+{
+    Push(Box<dyn Node<Frame = T> + Sync + Send + 'a, A>),
+    Pop,
+    Insert(usize, Box<dyn Node<Frame = T> + Sync + Send + 'a, A>),
+    Remove(usize),
+    Replace(usize, Box<dyn Node<Frame = T> + Sync + Send + 'a, A>),
+}
+
+impl<'a, T, A> Debug for Edit<'a, T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            Edit::Push(_) => f.write_str("Push(Box<dyn Node>)"),
+            Edit::Pop => f.write_str("Pop"),
+            Edit::Insert(i, _) => write!(f, "Insert({i}, Box<dyn Node>)"),
+            Edit::Remove(i) => write!(f, "Remove({i})"),
+            Edit::Replace(i, _) => write!(f, "Replace({i}, Box<dyn Node>)"),
+        }
+    }
+}
+
+/// [`Bus`] that can be edited from another thread.
+///
+/// Lives on the audio thread.  Pending edits are applied at the beginning of
+/// each call to [`proc`](Node::proc).  Removed nodes are handed back to the
+/// [`Editor`] to be dropped there, as are nodes retired by crossfades, and
+/// room for nodes is reserved up front, so no memory is allocated or
+/// deallocated here.  If the editor does not
+/// collect removed nodes, edits are postponed until it does.
+pub struct Remote<'a, T, A = Global>
+where
+    A: Allocator,
+{
+    bus:   Bus<'a, T, A>,
+    edits: Receiver<Edit<'a, T, A>, A>,
+    trash: Sender<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>, A>,
+}
+
+/// Non-real-time end of a [`Remote`] bus.
+///
+/// Dropping the editor drops the nodes removed from the bus so far.  Nodes
+/// removed afterwards are kept by the remote until it is itself dropped,
+/// never while processing.
+pub struct Editor<'a, T, A = Global>
+where
+    A: Allocator,
+{
+    edits: Sender<Edit<'a, T, A>, A>,
+    trash: Receiver<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>, A>,
+}
+
+impl<'a, T, A> Remote<'a, T, A>
+where
+    A: Allocator,
+{
+    /// Wrap `bus` in a remote with room for `capacity` pending edits and
+    /// for `max_nodes` nodes in the bus.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity == 0`.
+    pub fn new_in(
+        mut bus: Bus<'a, T, A>,
+        capacity: usize,
+        max_nodes: usize,
+        alloc: A,
+    ) -> (Self, Editor<'a, T, A>)
+    where
+        A: Clone,
+    {
+        bus.reserve(max_nodes.saturating_sub(bus.len()));
+        let (edits_tx, edits_rx) = spsc_in(capacity, alloc.clone());
+        let (trash_tx, trash_rx) = spsc_in(capacity, alloc);
+        (
+            Self {
+                bus,
+                edits: edits_rx,
+                trash: trash_tx,
+            },
+            Editor {
+                edits: edits_tx,
+                trash: trash_rx,
+            },
+        )
+    }
+
+    pub fn bus(&self) -> &Bus<'a, T, A> {
+        &self.bus
+    }
+
+    pub fn bus_mut(&mut self) -> &mut Bus<'a, T, A> {
+        &mut self.bus
+    }
+
+    pub fn into_bus(self) -> Bus<'a, T, A> {
+        self.bus
+    }
+
+    /// Apply pending edits, and hand back retired nodes of the bus.
+    ///
+    /// Called automatically by [`proc`](Node::proc).
+    pub fn apply(&mut self) {
+        self.retire();
+        while !self.trash.is_full() {
+            let Some(edit) = self.edits.recv() else {
+                break;
+            };
+            let len = self.bus.len();
+            let full = len == self.bus.capacity();
+            let removed = match edit {
+                Edit::Push(node) if !full => {
+                    self.bus.push(node);
+                    None
+                }
+                Edit::Pop => self.bus.pop(),
+                Edit::Insert(index, node) if index <= len && !full => {
+                    self.bus.insert(index, node);
+                    None
+                }
+                Edit::Remove(index) if index < len => {
+                    Some(self.bus.remove(index))
+                }
+                Edit::Replace(index, node) if index < len => {
+                    Some(self.bus.replace(index, node))
+                }
+                Edit::Push(node)
+                | Edit::Insert(_, node)
+                | Edit::Replace(_, node) => Some(node),
+                Edit::Remove(_) => None,
+            };
+            if let Some(node) = removed {
+                let _ = self.trash.send(node);
+            }
+            self.retire();
+        }
+    }
+
+    // Send nodes retired by crossfades to the editor, while there is room.
+    fn retire(&mut self) {
+        while !self.trash.is_full() {
+            let Some(node) = self.bus.take_retired() else {
+                break;
+            };
+            let _ = self.trash.send(node);
+        }
+    }
+}
+
+impl<'a, T> Remote<'a, T> {
+    /// # Panics
+    ///
+    /// Panics if `capacity == 0`.
+    #[must_use]
+    pub fn new(
+        bus: Bus<'a, T>,
+        capacity: usize,
+        max_nodes: usize,
+    ) -> (Self, Editor<'a, T>) {
+        Self::new_in(bus, capacity, max_nodes, Global)
+    }
+}
+
+impl<'a, T, A> Node for Remote<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        self.apply();
+        self.bus.proc(frames);
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.bus.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.bus.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.bus.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.bus.visit_params(visitor);
+    }
+}
+
+impl<'a, T, A> Editor<'a, T, A>
+where
+    A: Allocator,
+{
+    /// Send an edit to the audio thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn send(
+        &mut self,
+        edit: Edit<'a, T, A>,
+    ) -> Result<(), Edit<'a, T, A>> {
+        self.edits.send(edit)
+    }
+
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn push(
+        &mut self,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Result<(), Edit<'a, T, A>> {
+        self.send(Edit::Push(node))
+    }
+
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn pop(&mut self) -> Result<(), Edit<'a, T, A>> {
+        self.send(Edit::Pop)
+    }
+
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn insert(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Result<(), Edit<'a, T, A>> {
+        self.send(Edit::Insert(index, node))
+    }
+
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn remove(
+        &mut self,
+        index: usize,
+    ) -> Result<(), Edit<'a, T, A>> {
+        self.send(Edit::Remove(index))
+    }
+
+    /// # Errors
+    ///
+    /// Returns the edit, if the queue is full.
+    pub fn replace(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Result<(), Edit<'a, T, A>> {
+        self.send(Edit::Replace(index, node))
+    }
+
+    /// Take a node removed from the bus, if any.
+    pub fn recv(
+        &mut self
+    ) -> Option<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>> {
+        self.trash.recv()
+    }
+
+    /// Drop all nodes removed from the bus so far.
+    ///
+    /// Returns the number of nodes dropped.
+    pub fn collect(&mut self) -> usize {
+        let mut count = 0;
+        while self.recv().is_some() {
+            count += 1;
+        }
+        count
+    }
+}
+
+impl<'a, T, A> Drop for Editor<'a, T, A>
+where
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        self.collect();
+    }
+}
+
+impl<'a, T, A> Debug for Remote<'a, T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Remote")
+            .field("bus", &self.bus)
+            .field("edits", &self.edits)
+            .field("trash", &self.trash)
+            .finish()
+    }
+}
+
+impl<'a, T, A> Debug for Editor<'a, T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Editor")
+            .field("edits", &self.edits)
+            .field("trash", &self.trash)
+            .finish()
+    }
+}
@@ -0,0 +1,108 @@
+use crate::{
+    node::Node,
+    num::Fp,
+};
+
+/// Processing context.
+///
+/// Frequencies of oscillators and filters are fractions of the sample rate.
+/// The context converts physical units to such normalized quantities.  It
+/// also keeps track of the transport: the position of the current frame and
+/// tempo.  Hosts set the context at prepare time and advance it after each
+/// processed block.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Context {
+    /// Samples per second.
+    pub sample_rate: Fp,
+    /// Maximal number of frames processed at once.
+    pub max_block:   usize,
+    /// Frame position of the transport.
+    pub position:    u64,
+    /// Beats per minute.
+    pub tempo:       Fp,
+}
+
+impl Context {
+    /// Context at position zero and tempo 120 BPM.
+    #[must_use]
+    pub fn new(
+        sample_rate: Fp,
+        max_block: usize,
+    ) -> Self {
+        Self {
+            sample_rate,
+            max_block,
+            position: 0,
+            tempo: 120.,
+        }
+    }
+
+    #[must_use]
+    pub fn with_tempo(
+        self,
+        tempo: Fp,
+    ) -> Self {
+        Self {
+            tempo,
+            ..self
+        }
+    }
+
+    /// Frequency in Hz as a fraction of the sample rate.
+    #[must_use]
+    pub fn hz(
+        &self,
+        freq: Fp,
+    ) -> Fp {
+        freq / self.sample_rate
+    }
+
+    /// Duration in seconds as a number of frames.
+    #[must_use]
+    pub fn secs(
+        &self,
+        secs: Fp,
+    ) -> Fp {
+        secs * self.sample_rate
+    }
+
+    /// Duration in beats as a number of frames.
+    #[must_use]
+    pub fn beats(
+        &self,
+        beats: Fp,
+    ) -> Fp {
+        self.secs(beats * 60. / self.tempo)
+    }
+
+    /// Position of the transport in seconds.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn time(&self) -> Fp {
+        self.position as Fp / self.sample_rate
+    }
+
+    /// Position of the transport in beats.
+    #[must_use]
+    pub fn beat(&self) -> Fp {
+        self.time() * self.tempo / 60.
+    }
+
+    /// Prepare `node` for processing with this context.
+    pub fn prepare<N>(
+        &self,
+        node: &mut N,
+    ) where
+        N: Node + ?Sized,
+    {
+        node.prepare(self.max_block, self.sample_rate);
+    }
+
+    /// Move the transport forward.
+    pub fn advance(
+        &mut self,
+        frames: usize,
+    ) {
+        self.position += frames as u64;
+    }
+}
@@ -1,7 +1,15 @@
+use crate::num::Fp;
+
 pub trait Reset {
     fn reset(&mut self);
 }
 
+/// Access to parameters and state through a handle.
+///
+/// The handle borrows from `self` for the duration of the callback.  Bounds
+/// on handles of all lifetimes, e.g. in the blanket [`Reset`] implementation,
+/// currently imply `Self: 'static`, so built-in nodes implement `Control`
+/// only for `'static` frames and borrows.
 pub trait Control {
     type Ctl<'a>
     where
@@ -22,3 +30,25 @@ where
         self.control(|ctl| ctl.reset());
     }
 }
+
+/// Visitor of parameters of a node.
+///
+/// See [`Node::visit_params`](crate::node::Node::visit_params).
+pub trait Visit {
+    /// Visit the parameter `name`.
+    fn param(
+        &mut self,
+        name: &str,
+        value: &mut Fp,
+    );
+
+    /// Enter the child node at `index`.  Does nothing by default.
+    fn enter(
+        &mut self,
+        _index: usize,
+    ) {
+    }
+
+    /// Leave the child node entered last.  Does nothing by default.
+    fn leave(&mut self) {}
+}
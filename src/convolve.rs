@@ -0,0 +1,259 @@
+//! Convolution with long impulse responses.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    fmt::Debug,
+};
+
+use crate::{
+    fft::RealFft,
+    frame::Frame,
+    node::Node,
+    num::{
+        Complex,
+        Float,
+        Fp,
+    },
+    Buf,
+};
+
+/// Uniformly partitioned convolution in the frequency domain.
+///
+/// The impulse response is split into partitions of `block` frames, whose
+/// spectra are computed up front.  Input is collected in blocks of the same
+/// size, transformed, and multiplied with the partitions in a frequency
+/// domain delay line (overlap-save).  The latency is exactly `block` frames.
+///
+/// Each channel of the frame is convolved with the matching channel of the
+/// impulse response, so stereo and quad frames can use distinct responses.
+/// All buffers are allocated on construction.
+pub struct Convolver<T, A = Global>
+where
+    A: Allocator,
+{
+    block:    usize,
+    parts:    usize,
+    channels: usize,
+    fft:      RealFft<A>,
+    // spectra of partitions: [channel][partition][block + 1]
+    ir:       Buf<Complex, A>,
+    // spectra of past input blocks, in the same layout, used as a ring
+    fdl:      Buf<Complex, A>,
+    fdl_pos:  usize,
+    // previous and current input block
+    input:    Buf<T, A>,
+    output:   Buf<T, A>,
+    fill:     usize,
+    // one channel of the input, or of the output, in the time domain
+    samples:  Buf<Fp, A>,
+    acc:      Buf<Complex, A>,
+}
+
+impl<T, A> Convolver<T, A>
+where
+    A: Allocator + Clone,
+    T: Frame,
+{
+    /// Convolver with impulse response `ir`, processed in blocks of `block`
+    /// frames.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ir` is empty or `block` is not a power of two.
+    pub fn new_in(
+        ir: &[T],
+        block: usize,
+        alloc: A,
+    ) -> Self {
+        assert!(!ir.is_empty(), "impulse response must not be empty");
+        assert!(block.is_power_of_two(), "block must be a power of two");
+        let size = 2 * block;
+        let bins = block + 1;
+        let parts = ir.len().div_ceil(block);
+        let channels = T::zero().as_slice().len();
+
+        let mut conv = Self {
+            block,
+            parts,
+            channels,
+            fft: RealFft::new_in(size, alloc.clone()),
+            ir: Buf::alloc_new_in(channels * parts * bins, alloc.clone()),
+            fdl: Buf::alloc_new_in(channels * parts * bins, alloc.clone()),
+            fdl_pos: 0,
+            input: Buf::alloc_new_in(size, alloc.clone()),
+            output: Buf::alloc_new_in(block, alloc.clone()),
+            fill: 0,
+            samples: Buf::alloc_new_in(size, alloc.clone()),
+            acc: Buf::alloc_new_in(bins, alloc),
+        };
+        for ch in 0..channels {
+            for (p, part) in ir.chunks(block).enumerate() {
+                // zero padded to `size`
+                conv.samples.zero();
+                for (x, frm) in conv.samples.iter_mut().zip(part) {
+                    *x = frm.as_slice()[ch].to_float();
+                }
+                let offset = conv.offset(ch, p);
+                conv.fft.forward(
+                    conv.samples.as_slice(),
+                    &mut conv.ir.as_mut_slice()[offset..offset + bins],
+                );
+            }
+        }
+        conv
+    }
+}
+
+impl<T, A> Convolver<T, A>
+where
+    A: Allocator,
+{
+    /// Number of frames processed at once.
+    #[must_use]
+    pub fn block(&self) -> usize {
+        self.block
+    }
+
+    /// Number of channels of the frame.
+    #[must_use]
+    pub fn channels(&self) -> usize {
+        self.channels
+    }
+
+    /// Number of partitions of the impulse response.
+    #[must_use]
+    pub fn parts(&self) -> usize {
+        self.parts
+    }
+
+    fn offset(
+        &self,
+        ch: usize,
+        p: usize,
+    ) -> usize {
+        (ch * self.parts + p) * (self.block + 1)
+    }
+}
+
+impl<T, A> Convolver<T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    // Convolve the collected block, one channel at a time.
+    #[allow(clippy::cast_precision_loss)]
+    fn proc_block(&mut self) {
+        let bins = self.block + 1;
+        // the inverse transform is unnormalized
+        let norm = ((2 * self.block) as Fp).recip();
+        for ch in 0..self.channels {
+            for (x, frm) in self.samples.iter_mut().zip(&self.input) {
+                *x = frm.as_slice()[ch].to_float();
+            }
+            let pos = self.offset(ch, self.fdl_pos);
+            self.fft.forward(
+                self.samples.as_slice(),
+                &mut self.fdl.as_mut_slice()[pos..pos + bins],
+            );
+
+            self.acc.zero();
+            for p in 0..self.parts {
+                // input delayed by `p` blocks meets partition `p`
+                let delayed = (self.fdl_pos + self.parts - p) % self.parts;
+                let x = self.offset(ch, delayed);
+                let h = self.offset(ch, p);
+                let x = &self.fdl.as_slice()[x..x + bins];
+                let h = &self.ir.as_slice()[h..h + bins];
+                for ((y, x), h) in self.acc.iter_mut().zip(x).zip(h) {
+                    *y += *x * *h;
+                }
+            }
+            self.fft
+                .inverse(self.acc.as_mut_slice(), self.samples.as_mut_slice());
+            // the first half is aliased
+            for (frm, y) in self
+                .output
+                .iter_mut()
+                .zip(&self.samples.as_slice()[self.block..])
+            {
+                frm.as_mut_slice()[ch] = T::Sample::from_float(y * norm);
+            }
+        }
+        self.fdl_pos = (self.fdl_pos + 1) % self.parts;
+        let (prev, curr) = self.input.as_mut_slice().split_at_mut(self.block);
+        prev.copy_from_slice(curr);
+    }
+}
+
+impl<T> Convolver<T>
+where
+    T: Frame,
+{
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `ir` is empty or `block` is not a power of two.
+    #[must_use]
+    pub fn new(
+        ir: &[T],
+        block: usize,
+    ) -> Self {
+        Self::new_in(ir, block, Global)
+    }
+}
+
+impl<T, A> Node for Convolver<T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        for frm in frames {
+            self.input[self.block + self.fill] = *frm;
+            *frm = self.output[self.fill];
+            self.fill += 1;
+            if self.fill == self.block {
+                self.proc_block();
+                self.fill = 0;
+            }
+        }
+    }
+
+    /// Size of the block.
+    fn latency(&self) -> usize {
+        self.block
+    }
+
+    fn reset_state(&mut self) {
+        self.fdl.zero();
+        self.input.zero();
+        self.output.zero();
+        self.fdl_pos = 0;
+        self.fill = 0;
+    }
+}
+
+impl<T, A> Debug for Convolver<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Convolver")
+            .field("block", &self.block)
+            .field("parts", &self.parts)
+            .field("channels", &self.channels)
+            .finish_non_exhaustive()
+    }
+}
@@ -8,6 +8,11 @@ use std::{
 
 use crate::{
     bus::Bus,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
     frame::Frame,
     node::Node,
     num::{
@@ -51,6 +56,48 @@ impl<T: Frame> Node for Single<T> {
             mem::swap(&mut self.0, frm);
         }
     }
+
+    /// One frame.
+    fn latency(&self) -> usize {
+        1
+    }
+
+    fn reset_state(&mut self) {
+        self.0 = zero();
+    }
+}
+
+/// Handle to the state of [`Single`].
+///
+/// Reset clears the delayed frame.
+#[derive(Debug)]
+pub struct SingleCtl<'a, T> {
+    frame: &'a mut T,
+}
+
+impl<'a, T> Reset for SingleCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.frame = zero();
+    }
+}
+
+impl<T> Control for Single<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = SingleCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut SingleCtl {
+            frame: &mut self.0
+        });
+    }
 }
 
 #[derive(Debug)]
@@ -87,6 +134,13 @@ where
     pub fn into_buffer(self) -> Buf<T, A> {
         self.buffer
     }
+
+    fn ctl(&mut self) -> DelCtl<'_, T> {
+        DelCtl {
+            buffer: self.buffer.as_mut_slice(),
+            index:  &mut self.index,
+        }
+    }
 }
 
 impl<T, A> From<Buf<T, A>> for Del<T, A>
@@ -144,6 +198,62 @@ where
             *frm = y0;
         }
     }
+
+    /// Length of the buffer.
+    fn latency(&self) -> usize {
+        self.buffer.len()
+    }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+}
+
+/// Handle to the buffer of [`Del`].
+///
+/// Reset clears the buffer.
+#[derive(Debug)]
+pub struct DelCtl<'a, T> {
+    buffer: &'a mut [T],
+    index:  &'a mut usize,
+}
+
+impl<'a, T> DelCtl<'a, T> {
+    /// Length of the delay in frames.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+}
+
+impl<'a, T> Reset for DelCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        self.buffer.fill(zero());
+        *self.index = 0;
+    }
+}
+
+impl<T, A> Control for Del<T, A>
+where
+    A: Allocator + 'static,
+    T: Frame + 'static,
+{
+    type Ctl<'a> = DelCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
 }
 
 #[derive(Debug)]
@@ -212,6 +322,14 @@ where
     pub fn feedback_mut(&mut self) -> &mut Fp {
         &mut self.feedback
     }
+
+    fn ctl(&mut self) -> FbkCtl<'_, 'a, T, A> {
+        FbkCtl {
+            feedback:     &mut self.feedback,
+            bus:          &mut self.bus,
+            ss_del_frame: &mut self.ss_del_frame,
+        }
+    }
 }
 
 impl<'a, T, A> Node for Fbk<'a, T, A>
@@ -233,4 +351,70 @@ where
             *frm = y0;
         }
     }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.bus.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.bus.latency()
+    }
+
+    /// Clear the frame fed back and the state of all nodes on the bus.
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+
+    /// Visit `feedback`, then the parameters of the bus.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.param("feedback", &mut self.feedback);
+        self.bus.visit_params(visitor);
+    }
+}
+
+/// Handle to parameters and state of [`Fbk`].
+///
+/// Reset clears the frame fed back and the state of all nodes on the
+/// bus.
+#[derive(Debug)]
+pub struct FbkCtl<'a, 'b, T, A = Global>
+where
+    A: Allocator,
+{
+    pub feedback: &'a mut Fp,
+    pub bus:      &'a mut Bus<'b, T, A>,
+    ss_del_frame: &'a mut T,
+}
+
+impl<'a, 'b, T, A> Reset for FbkCtl<'a, 'b, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.ss_del_frame = zero();
+        self.bus.reset_state();
+    }
+}
+
+impl<T, A> Control for Fbk<'static, T, A>
+where
+    A: Allocator + 'static,
+    T: Frame + 'static,
+{
+    type Ctl<'a> = FbkCtl<'a, 'static, T, A> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
 }
@@ -0,0 +1,681 @@
+//! Fast Fourier transform and short-time Fourier transform.
+//!
+//! Transforms are planned up front: twiddle factors and scratch buffers are
+//! allocated on construction, so no memory is allocated while processing.
+//! Sizes are powers of two.  Transforms are unnormalized: the forward
+//! transform followed by the inverse one scales by the size.
+
+use std::alloc::{
+    Allocator,
+    Global,
+};
+
+use crate::{
+    num::{
+        Complex,
+        Fp,
+        Real,
+    },
+    window::Window,
+    Buf,
+};
+
+/// Radix-2 complex FFT.
+#[derive(Debug)]
+pub struct Fft<A = Global>
+where
+    A: Allocator,
+{
+    size:     usize,
+    twiddles: Buf<Complex, A>,
+}
+
+impl<A> Fft<A>
+where
+    A: Allocator,
+{
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new_in(
+        size: usize,
+        alloc: A,
+    ) -> Self {
+        assert!(size.is_power_of_two(), "size must be a power of two");
+        let mut twiddles = Buf::alloc_new_in(size / 2, alloc);
+        for (k, w) in twiddles.iter_mut().enumerate() {
+            *w = Complex::cis(-<Fp as Real>::TAU * k as Fp / size as Fp);
+        }
+        Self {
+            size,
+            twiddles,
+        }
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.size
+    }
+
+    /// Always false: the size is at least one.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+
+    /// Transform `data` in place.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` differs from the size.
+    pub fn forward(
+        &self,
+        data: &mut [Complex],
+    ) {
+        self.transform(data, false);
+    }
+
+    /// Inverse transform `data` in place, unnormalized.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the length of `data` differs from the size.
+    pub fn inverse(
+        &self,
+        data: &mut [Complex],
+    ) {
+        self.transform(data, true);
+    }
+
+    fn transform(
+        &self,
+        data: &mut [Complex],
+        inverse: bool,
+    ) {
+        let n = self.size;
+        assert_eq!(data.len(), n, "data length must match the size");
+
+        // bit-reversal permutation
+        let mut j = 0;
+        for i in 1..n {
+            let mut bit = n >> 1;
+            while j & bit != 0 {
+                j ^= bit;
+                bit >>= 1;
+            }
+            j |= bit;
+            if i < j {
+                data.swap(i, j);
+            }
+        }
+
+        let mut len = 2;
+        while len <= n {
+            let half = len / 2;
+            let step = n / len;
+            for start in (0..n).step_by(len) {
+                for k in 0..half {
+                    let w = self.twiddles[k * step];
+                    let w = if inverse { w.conj() } else { w };
+                    let u = data[start + k];
+                    let v = data[start + k + half] * w;
+                    data[start + k] = u + v;
+                    data[start + k + half] = u - v;
+                }
+            }
+            len <<= 1;
+        }
+    }
+}
+
+impl Fft {
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self::new_in(size, Global)
+    }
+}
+
+/// FFT of real signals.
+///
+/// A signal of `size` samples has `size / 2 + 1` bins, from DC to Nyquist.
+/// It is computed with a complex FFT of half the size.
+#[derive(Debug)]
+pub struct RealFft<A = Global>
+where
+    A: Allocator,
+{
+    fft:      Fft<A>,
+    // `exp(-2 PI i k / size)` for `k < size / 2`
+    twiddles: Buf<Complex, A>,
+}
+
+impl<A> RealFft<A>
+where
+    A: Allocator + Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new_in(
+        size: usize,
+        alloc: A,
+    ) -> Self {
+        assert!(
+            size >= 2 && size.is_power_of_two(),
+            "size must be a power of two of at least 2"
+        );
+        let mut twiddles = Buf::alloc_new_in(size / 2, alloc.clone());
+        for (k, w) in twiddles.iter_mut().enumerate() {
+            *w = Complex::cis(-<Fp as Real>::TAU * k as Fp / size as Fp);
+        }
+        Self {
+            fft: Fft::new_in(size / 2, alloc),
+            twiddles,
+        }
+    }
+}
+
+impl<A> RealFft<A>
+where
+    A: Allocator,
+{
+    /// Number of samples.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        2 * self.fft.len()
+    }
+
+    /// Always false: the size is at least two.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Number of bins: `len() / 2 + 1`.
+    #[must_use]
+    pub fn bins(&self) -> usize {
+        self.fft.len() + 1
+    }
+
+    /// Transform `input` into `output`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` has not [`len`](Self::len) samples or `output` has
+    /// not [`bins`](Self::bins) bins.
+    pub fn forward(
+        &self,
+        input: &[Fp],
+        output: &mut [Complex],
+    ) {
+        let m = self.fft.len();
+        assert_eq!(input.len(), 2 * m, "input length must match the size");
+        assert_eq!(output.len(), m + 1, "output length must match the bins");
+
+        // pack even samples into real parts, odd ones into imaginary parts
+        for (z, x) in output.iter_mut().zip(input.chunks_exact(2)) {
+            *z = Complex::new(x[0], x[1]);
+        }
+        self.fft.forward(&mut output[..m]);
+
+        // separate spectra of even and odd samples and combine them
+        let z0 = output[0];
+        output[0] = Complex::new(z0.re + z0.im, 0.);
+        output[m] = Complex::new(z0.re - z0.im, 0.);
+        for k in 1..=m / 2 {
+            let (a, b) = (output[k], output[m - k]);
+            output[k] = self.combine(k, a, b);
+            output[m - k] = self.combine(m - k, b, a);
+        }
+    }
+
+    // Bin `k` from bins `k` and `m - k` of the packed spectrum.
+    fn combine(
+        &self,
+        k: usize,
+        a: Complex,
+        b: Complex,
+    ) -> Complex {
+        let even = (a + b.conj()) * 0.5;
+        let odd = a - b.conj();
+        // divide by `2i`
+        let odd = Complex::new(odd.im, -odd.re) * 0.5;
+        even + self.twiddles[k] * odd
+    }
+
+    /// Inverse transform `input` into `output`, unnormalized.
+    ///
+    /// `input` is used as scratch space and left unspecified.  Imaginary
+    /// parts of DC and Nyquist bins are ignored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `input` has not [`bins`](Self::bins) bins or `output` has
+    /// not [`len`](Self::len) samples.
+    pub fn inverse(
+        &self,
+        input: &mut [Complex],
+        output: &mut [Fp],
+    ) {
+        let m = self.fft.len();
+        assert_eq!(input.len(), m + 1, "input length must match the bins");
+        assert_eq!(output.len(), 2 * m, "output length must match the size");
+
+        // pack spectra of even and odd samples
+        let (x0, xm) = (input[0].re, input[m].re);
+        input[0] = Complex::new(x0 + xm, x0 - xm);
+        for k in 1..=m / 2 {
+            let (a, b) = (input[k], input[m - k]);
+            input[k] = self.split(k, a, b);
+            input[m - k] = self.split(m - k, b, a);
+        }
+        self.fft.inverse(&mut input[..m]);
+
+        for (x, z) in output.chunks_exact_mut(2).zip(&input[..m]) {
+            x[0] = z.re;
+            x[1] = z.im;
+        }
+    }
+
+    // Bin `k` of the packed spectrum from bins `k` and `m - k`, scaled by 2.
+    fn split(
+        &self,
+        k: usize,
+        a: Complex,
+        b: Complex,
+    ) -> Complex {
+        let even = a + b.conj();
+        let odd = (a - b.conj()) * self.twiddles[k].conj();
+        // multiply by `i`
+        even + Complex::new(-odd.im, odd.re)
+    }
+}
+
+impl RealFft {
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2.
+    #[must_use]
+    pub fn new(size: usize) -> Self {
+        Self::new_in(size, Global)
+    }
+}
+
+/// Streaming short-time Fourier transform.
+///
+/// Samples are collected in frames of `size` samples, taken every `hop`
+/// samples, windowed and transformed.
+#[derive(Debug)]
+pub struct Stft<A = Global>
+where
+    A: Allocator,
+{
+    fft:      RealFft<A>,
+    hop:      usize,
+    window:   Buf<Fp, A>,
+    // last `size` samples; the newest `fill` ones are not analyzed yet
+    input:    Buf<Fp, A>,
+    fill:     usize,
+    frame:    Buf<Fp, A>,
+    spectrum: Buf<Complex, A>,
+}
+
+impl<A> Stft<A>
+where
+    A: Allocator + Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    pub fn new_in(
+        size: usize,
+        hop: usize,
+        window: Window,
+        alloc: A,
+    ) -> Self {
+        assert!(hop > 0 && hop <= size, "hop must be in 1..=size");
+        let fft = RealFft::new_in(size, alloc.clone());
+        let bins = fft.bins();
+        let mut win = Buf::alloc_new_in(size, alloc.clone());
+        window.fill_periodic(win.as_mut_slice());
+        Self {
+            fft,
+            hop,
+            window: win,
+            input: Buf::alloc_new_in(size, alloc.clone()),
+            fill: 0,
+            frame: Buf::alloc_new_in(size, alloc.clone()),
+            spectrum: Buf::alloc_new_in(bins, alloc),
+        }
+    }
+}
+
+impl<A> Stft<A>
+where
+    A: Allocator,
+{
+    /// Number of samples in a frame.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.fft.len()
+    }
+
+    #[must_use]
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Number of bins of the spectrum.
+    #[must_use]
+    pub fn bins(&self) -> usize {
+        self.fft.bins()
+    }
+
+    /// Analysis window.
+    pub fn window(&self) -> &[Fp] {
+        self.window.as_slice()
+    }
+
+    /// Push `samples` and call `f` with the spectrum of every completed
+    /// frame.
+    pub fn analyze(
+        &mut self,
+        samples: &[Fp],
+        mut f: impl FnMut(&mut [Complex]),
+    ) {
+        let size = self.size();
+        let mut samples = samples;
+        while !samples.is_empty() {
+            let len = (self.hop - self.fill).min(samples.len());
+            let start = size - self.hop + self.fill;
+            self.input.as_mut_slice()[start..start + len]
+                .copy_from_slice(&samples[..len]);
+            self.fill += len;
+            samples = &samples[len..];
+
+            if self.fill == self.hop {
+                for ((y, x), w) in
+                    self.frame.iter_mut().zip(&self.input).zip(&self.window)
+                {
+                    *y = x * w;
+                }
+                self.fft.forward(
+                    self.frame.as_slice(),
+                    self.spectrum.as_mut_slice(),
+                );
+                f(self.spectrum.as_mut_slice());
+                self.input.as_mut_slice().copy_within(self.hop.., 0);
+                self.fill = 0;
+            }
+        }
+    }
+
+    /// Clear collected samples.
+    pub fn reset(&mut self) {
+        self.input.zero();
+        self.fill = 0;
+    }
+}
+
+impl Stft {
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    #[must_use]
+    pub fn new(
+        size: usize,
+        hop: usize,
+        window: Window,
+    ) -> Self {
+        Self::new_in(size, hop, window, Global)
+    }
+}
+
+/// Streaming inverse short-time Fourier transform.
+///
+/// Every spectrum is transformed back, windowed, and added to the previous
+/// frames, overlapping by `size - hop` samples.  The output is normalized
+/// by the overlap of analysis and synthesis windows, so that analysis and
+/// synthesis with the same window reconstruct the signal, provided the
+/// squared window overlap-adds to a constant, like Hann with a hop of a
+/// quarter of the size.
+#[derive(Debug)]
+pub struct Istft<A = Global>
+where
+    A: Allocator,
+{
+    fft:    RealFft<A>,
+    hop:    usize,
+    window: Buf<Fp, A>,
+    norm:   Fp,
+    acc:    Buf<Fp, A>,
+    frame:  Buf<Fp, A>,
+}
+
+impl<A> Istft<A>
+where
+    A: Allocator + Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn new_in(
+        size: usize,
+        hop: usize,
+        window: Window,
+        alloc: A,
+    ) -> Self {
+        assert!(hop > 0 && hop <= size, "hop must be in 1..=size");
+        let mut win = Buf::alloc_new_in(size, alloc.clone());
+        window.fill_periodic(win.as_mut_slice());
+        let overlap: Fp = win.iter().map(|w| w * w).sum::<Fp>() / hop as Fp;
+        Self {
+            fft: RealFft::new_in(size, alloc.clone()),
+            hop,
+            window: win,
+            // the inverse transform is unnormalized
+            norm: (overlap * size as Fp).recip(),
+            acc: Buf::alloc_new_in(size, alloc.clone()),
+            frame: Buf::alloc_new_in(size, alloc),
+        }
+    }
+}
+
+impl<A> Istft<A>
+where
+    A: Allocator,
+{
+    /// Number of samples in a frame.
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.fft.len()
+    }
+
+    #[must_use]
+    pub fn hop(&self) -> usize {
+        self.hop
+    }
+
+    /// Add the frame of `spectrum` and return the next `hop` finished
+    /// samples.
+    ///
+    /// `spectrum` is used as scratch space and left unspecified.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `spectrum` has not `size / 2 + 1` bins.
+    pub fn synthesize(
+        &mut self,
+        spectrum: &mut [Complex],
+    ) -> &[Fp] {
+        self.fft.inverse(spectrum, self.frame.as_mut_slice());
+        for ((y, x), w) in
+            self.acc.iter_mut().zip(&self.frame).zip(&self.window)
+        {
+            *y += x * w * self.norm;
+        }
+        // the first `hop` samples are finished, the frame is free as scratch
+        let hop = self.hop;
+        self.frame.as_mut_slice()[..hop]
+            .copy_from_slice(&self.acc.as_slice()[..hop]);
+        let acc = self.acc.as_mut_slice();
+        acc.copy_within(hop.., 0);
+        let size = acc.len();
+        acc[size - hop..].fill(0.);
+        &self.frame.as_slice()[..hop]
+    }
+
+    /// Clear overlapping frames.
+    pub fn reset(&mut self) {
+        self.acc.zero();
+    }
+}
+
+impl Istft {
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    #[must_use]
+    pub fn new(
+        size: usize,
+        hop: usize,
+        window: Window,
+    ) -> Self {
+        Self::new_in(size, hop, window, Global)
+    }
+}
+
+/// Spectral processing of a stream in place: analysis, modification of
+/// each spectrum, and resynthesis with overlap-add.
+///
+/// The latency is `size` samples.
+#[derive(Debug)]
+pub struct StftProc<A = Global>
+where
+    A: Allocator,
+{
+    stft:   Stft<A>,
+    istft:  Istft<A>,
+    // last synthesized samples, played back while the next frame is
+    // collected
+    output: Buf<Fp, A>,
+    pos:    usize,
+}
+
+impl<A> StftProc<A>
+where
+    A: Allocator + Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    pub fn new_in(
+        size: usize,
+        hop: usize,
+        window: Window,
+        alloc: A,
+    ) -> Self {
+        Self {
+            stft:   Stft::new_in(size, hop, window, alloc.clone()),
+            istft:  Istft::new_in(size, hop, window, alloc.clone()),
+            output: Buf::alloc_new_in(hop, alloc),
+            pos:    0,
+        }
+    }
+}
+
+impl<A> StftProc<A>
+where
+    A: Allocator,
+{
+    #[must_use]
+    pub fn size(&self) -> usize {
+        self.stft.size()
+    }
+
+    #[must_use]
+    pub fn hop(&self) -> usize {
+        self.stft.hop()
+    }
+
+    #[must_use]
+    pub fn bins(&self) -> usize {
+        self.stft.bins()
+    }
+
+    /// Delay of the output in samples.
+    #[must_use]
+    pub fn latency(&self) -> usize {
+        self.size()
+    }
+
+    /// Process `samples` in place, calling `f` with every spectrum before
+    /// resynthesis.
+    pub fn proc(
+        &mut self,
+        samples: &mut [Fp],
+        mut f: impl FnMut(&mut [Complex]),
+    ) {
+        let Self {
+            stft,
+            istft,
+            output,
+            pos,
+        } = self;
+        for x in samples {
+            let input = *x;
+            *x = output[*pos];
+            *pos += 1;
+            stft.analyze(&[input], |spectrum| {
+                f(spectrum);
+                output
+                    .as_mut_slice()
+                    .copy_from_slice(istft.synthesize(spectrum));
+                *pos = 0;
+            });
+        }
+    }
+
+    pub fn reset(&mut self) {
+        self.stft.reset();
+        self.istft.reset();
+        self.output.zero();
+        self.pos = 0;
+    }
+}
+
+impl StftProc {
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is not a power of two of at least 2, or if `hop`
+    /// is zero or greater than `size`.
+    #[must_use]
+    pub fn new(
+        size: usize,
+        hop: usize,
+        window: Window,
+    ) -> Self {
+        Self::new_in(size, hop, window, Global)
+    }
+}
@@ -1,15 +1,122 @@
 use crate::{
+    context::Context,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
     frame::Frame,
     node::Node,
     num::{
         one,
         zero,
+        Complex,
         Float,
         Fp,
         Real,
     },
 };
 
+mod butter;
+mod crossover;
+mod fir;
+mod ladder;
+mod svf;
+
+pub use butter::{
+    bpf,
+    bpf2,
+    bpf4,
+    bpf8,
+    hpf,
+    hpf2,
+    hpf4,
+    hpf8,
+    lpf,
+    lpf2,
+    lpf4,
+    lpf8,
+    Bandpass,
+    Bpf,
+    BpfCtl,
+    Butter,
+    ButterCtl,
+    Design,
+    Hipass,
+    Hpf,
+    HpfCtl,
+    Lowpass,
+    Lpf,
+    LpfCtl,
+};
+pub use crossover::{
+    Crossover,
+    LrBand,
+    LrOrder,
+};
+pub use fir::{
+    design_fir,
+    Fir,
+    FirKind,
+};
+pub use ladder::{
+    ladder,
+    Ladder,
+    LadderCtl,
+    LadderSlope,
+};
+pub use svf::{
+    svf,
+    Svf,
+    SvfCtl,
+    SvfMode,
+    SvfOut,
+};
+
+// Half of the distance between frequencies at which the phase is evaluated
+// to estimate group delay.
+const GROUP_DELAY_DELTA: Fp = 1e-5;
+
+/// Frequency response of a linear filter.
+///
+/// Frequencies are fractions of the sample rate, from `0.` to `0.5`.
+pub trait Response {
+    /// Complex response at `freq`.
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex;
+
+    /// Magnitude of the response in dB.
+    fn gain_db(
+        &self,
+        freq: Fp,
+    ) -> Fp {
+        20. * self.response(freq).norm().log10()
+    }
+
+    /// Phase of the response in radians, in `(-PI, PI]`.
+    fn phase(
+        &self,
+        freq: Fp,
+    ) -> Fp {
+        self.response(freq).arg()
+    }
+
+    /// Group delay in frames, i.e. the negative derivative of phase with
+    /// respect to angular frequency.
+    ///
+    /// Estimated numerically by default.
+    fn group_delay(
+        &self,
+        freq: Fp,
+    ) -> Fp {
+        let d = GROUP_DELAY_DELTA;
+        let ratio = self.response(freq + d) / self.response(freq - d);
+        -ratio.arg() / (<Fp as Real>::TAU * 2. * d)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OnePole<T>
 where
@@ -32,6 +139,14 @@ where
             y1: zero(),
         }
     }
+
+    fn ctl(&mut self) -> OnePoleCtl<'_, T> {
+        OnePoleCtl {
+            b0: &mut self.b0,
+            a1: &mut self.a1,
+            y1: &mut self.y1,
+        }
+    }
 }
 
 impl<T> Default for OnePole<T>
@@ -56,6 +171,46 @@ impl<T: Frame> Node for OnePole<T> {
             *frm = y0;
         }
     }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+}
+
+/// Handle to parameters and state of [`OnePole`].
+///
+/// Reset clears the filter history.
+#[derive(Debug)]
+pub struct OnePoleCtl<'a, T>
+where
+    T: Frame,
+{
+    pub b0: &'a mut T::Sample,
+    pub a1: &'a mut T::Sample,
+    y1:     &'a mut T,
+}
+
+impl<'a, T> Reset for OnePoleCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.y1 = zero();
+    }
+}
+
+impl<T> Control for OnePole<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = OnePoleCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
 }
 
 /// DC Blocking filter
@@ -78,6 +233,23 @@ where
             y1: zero(),
         }
     }
+
+    /// DC blocker with the -3 dB point in Hz.
+    #[must_use]
+    pub fn hz(
+        cutoff: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(<Fp as Real>::TAU * ctx.hz(cutoff))
+    }
+
+    fn ctl(&mut self) -> DCBlockCtl<'_, T> {
+        DCBlockCtl {
+            cutoff: &mut self.cutoff,
+            x1:     &mut self.x1,
+            y1:     &mut self.y1,
+        }
+    }
 }
 
 impl<T> From<Fp> for DCBlock<T>
@@ -106,11 +278,68 @@ where
             *frm = y0;
         }
     }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.param("cutoff", &mut self.cutoff);
+    }
+}
+
+/// Handle to parameters and state of [`DCBlock`].
+///
+/// Reset clears the filter history.
+#[derive(Debug)]
+pub struct DCBlockCtl<'a, T> {
+    pub cutoff: &'a mut Fp,
+    x1:         &'a mut T,
+    y1:         &'a mut T,
+}
+
+impl<'a, T> Reset for DCBlockCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.x1 = zero();
+        *self.y1 = zero();
+    }
+}
+
+impl<T> Control for DCBlock<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = DCBlockCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
+}
+
+impl<T> Response for DCBlock<T> {
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        let z1 = Complex::cis(-<Fp as Real>::TAU * freq);
+        let num = Complex::from(1.) - z1;
+        let den = Complex::from(1.) - z1 * (1.0 - self.cutoff);
+        num / den
+    }
 }
 
 // Biquad filter from [`FunDSP`](https://github.com/SamiPerttu/fundsp)
 // by Sami Perttu. `FunDSP` is licensed under MIT License.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct BiquadCoefs {
     pub a1: Fp,
     pub a2: Fp,
@@ -166,7 +395,7 @@ impl BiquadCoefs {
         let a0r: Fp = 1.0 / (1.0 + <Fp as Real>::SQRT_2 * f + f * f);
         let a1: Fp = (2.0 * f * f - 2.0) * a0r;
         let a2: Fp = (1.0 - <Fp as Real>::SQRT_2 * f + f * f) * a0r;
-        let b0: Fp = 1. - f * f * a0r;
+        let b0: Fp = a0r;
         let b1: Fp = -2.0 * b0;
         let b2: Fp = b0;
         Self {
@@ -199,6 +428,226 @@ impl BiquadCoefs {
             b2,
         }
     }
+
+    // Normalize coefficients from the Audio EQ Cookbook by `a0`.
+    fn cookbook(
+        b0: Fp,
+        b1: Fp,
+        b2: Fp,
+        a0: Fp,
+        a1: Fp,
+        a2: Fp,
+    ) -> Self {
+        let a0r = 1.0 / a0;
+        Self {
+            a1: a1 * a0r,
+            a2: a2 * a0r,
+            b0: b0 * a0r,
+            b1: b1 * a0r,
+            b2: b2 * a0r,
+        }
+    }
+
+    // Cosine of the angular frequency and `alpha` from the cookbook.
+    fn cos_alpha(
+        freq: Fp,
+        q: Fp,
+    ) -> (Fp, Fp) {
+        let w0: Fp = <Fp as Real>::TAU * freq;
+        (<Fp as Real>::cos(w0), <Fp as Real>::sin(w0) / (2.0 * q))
+    }
+
+    /// Lowpass filter with resonance `q` from the Audio EQ Cookbook by
+    /// Robert Bristow-Johnson.
+    ///
+    /// With `q = 1/sqrt(2)`, it is the Butterworth lowpass.
+    #[must_use]
+    pub fn lowpass(
+        cutoff: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(cutoff, q);
+        let b1: Fp = 1.0 - cos;
+        let b0: Fp = b1 * 0.5;
+        Self::cookbook(b0, b1, b0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// Hipass filter with resonance `q` from the Audio EQ Cookbook.
+    #[must_use]
+    pub fn hipass(
+        cutoff: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(cutoff, q);
+        let b0: Fp = (1.0 + cos) * 0.5;
+        let b1: Fp = -(1.0 + cos);
+        Self::cookbook(b0, b1, b0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// Bandpass filter with constant skirt gain: the peak gain is `q`.
+    #[must_use]
+    pub fn bandpass(
+        center: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(center, q);
+        let b0: Fp = q * alpha;
+        Self::cookbook(b0, 0.0, -b0, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// Bandpass filter with constant 0 dB peak gain.
+    #[must_use]
+    pub fn bandpass_peak(
+        center: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(center, q);
+        Self::cookbook(alpha, 0.0, -alpha, 1.0 + alpha, -2.0 * cos, 1.0 - alpha)
+    }
+
+    /// Notch filter.
+    #[must_use]
+    pub fn notch(
+        center: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(center, q);
+        let b1: Fp = -2.0 * cos;
+        Self::cookbook(1.0, b1, 1.0, 1.0 + alpha, b1, 1.0 - alpha)
+    }
+
+    /// Allpass filter with the phase shift of 180 degrees at `center`.
+    #[must_use]
+    pub fn allpass(
+        center: Fp,
+        q: Fp,
+    ) -> Self {
+        let (cos, alpha) = Self::cos_alpha(center, q);
+        let b1: Fp = -2.0 * cos;
+        Self::cookbook(
+            1.0 - alpha,
+            b1,
+            1.0 + alpha,
+            1.0 + alpha,
+            b1,
+            1.0 - alpha,
+        )
+    }
+
+    /// Peaking EQ with `gain` in dB at `center`.
+    #[must_use]
+    pub fn peak(
+        center: Fp,
+        q: Fp,
+        gain: Fp,
+    ) -> Self {
+        let a: Fp = <Fp as Real>::powf(10.0, gain / 40.0);
+        let (cos, alpha) = Self::cos_alpha(center, q);
+        let b1: Fp = -2.0 * cos;
+        Self::cookbook(
+            1.0 + alpha * a,
+            b1,
+            1.0 - alpha * a,
+            1.0 + alpha / a,
+            b1,
+            1.0 - alpha / a,
+        )
+    }
+
+    /// Low shelf with `gain` in dB below `cutoff`.
+    ///
+    /// `q = 1/sqrt(2)` gives the steepest slope without overshoot.
+    #[must_use]
+    pub fn lowshelf(
+        cutoff: Fp,
+        q: Fp,
+        gain: Fp,
+    ) -> Self {
+        let a: Fp = <Fp as Real>::powf(10.0, gain / 40.0);
+        let (cos, alpha) = Self::cos_alpha(cutoff, q);
+        let s: Fp = 2.0 * <Fp as Real>::sqrt(a) * alpha;
+        Self::cookbook(
+            a * ((a + 1.0) - (a - 1.0) * cos + s),
+            2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+            a * ((a + 1.0) - (a - 1.0) * cos - s),
+            (a + 1.0) + (a - 1.0) * cos + s,
+            -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+            (a + 1.0) + (a - 1.0) * cos - s,
+        )
+    }
+
+    /// High shelf with `gain` in dB above `cutoff`.
+    ///
+    /// `q = 1/sqrt(2)` gives the steepest slope without overshoot.
+    #[must_use]
+    pub fn hishelf(
+        cutoff: Fp,
+        q: Fp,
+        gain: Fp,
+    ) -> Self {
+        let a: Fp = <Fp as Real>::powf(10.0, gain / 40.0);
+        let (cos, alpha) = Self::cos_alpha(cutoff, q);
+        let s: Fp = 2.0 * <Fp as Real>::sqrt(a) * alpha;
+        Self::cookbook(
+            a * ((a + 1.0) + (a - 1.0) * cos + s),
+            -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+            a * ((a + 1.0) + (a - 1.0) * cos - s),
+            (a + 1.0) - (a - 1.0) * cos + s,
+            2.0 * ((a - 1.0) - (a + 1.0) * cos),
+            (a + 1.0) - (a - 1.0) * cos - s,
+        )
+    }
+
+    /// Whether the filter is stable, i.e. its poles lie inside the unit
+    /// circle.
+    ///
+    /// Stable coefficients form a triangle in the `(a1, a2)` plane.
+    #[must_use]
+    pub fn is_stable(&self) -> bool {
+        self.a2.abs() < 1. && self.a1.abs() < 1. + self.a2
+    }
+
+    fn zip_with(
+        self,
+        other: Self,
+        f: impl Fn(Fp, Fp) -> Fp,
+    ) -> Self {
+        Self {
+            a1: f(self.a1, other.a1),
+            a2: f(self.a2, other.a2),
+            b0: f(self.b0, other.b0),
+            b1: f(self.b1, other.b1),
+            b2: f(self.b2, other.b2),
+        }
+    }
+
+    /// Butterworth lowpass filter with cutoff in Hz.
+    #[must_use]
+    pub fn butter_lowpass_hz(
+        cutoff: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::butter_lowpass(ctx.hz(cutoff))
+    }
+
+    /// Butterworth hipass filter with cutoff in Hz.
+    #[must_use]
+    pub fn butter_hipass_hz(
+        cutoff: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::butter_hipass(ctx.hz(cutoff))
+    }
+
+    /// Resonator with center and bandwidth in Hz.
+    #[must_use]
+    pub fn resonator_hz(
+        center: Fp,
+        bandwidth: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::resonator(ctx.hz(center), ctx.hz(bandwidth))
+    }
 }
 
 impl Default for BiquadCoefs {
@@ -213,10 +662,77 @@ impl Default for BiquadCoefs {
     }
 }
 
+// Linear interpolation of coefficients towards a target, frame by frame.
+// `last` holds the coefficients written by the glide: if they have been
+// assigned since, the glide is cancelled.
+#[derive(Debug, Clone, Copy, Default)]
+struct CoefsGlide {
+    target: BiquadCoefs,
+    step:   BiquadCoefs,
+    last:   BiquadCoefs,
+    remain: usize,
+}
+
+impl CoefsGlide {
+    #[allow(clippy::cast_precision_loss)]
+    fn start(
+        &mut self,
+        coefs: &mut BiquadCoefs,
+        target: BiquadCoefs,
+        frames: usize,
+    ) {
+        if frames == 0 {
+            *coefs = target;
+            self.remain = 0;
+        } else {
+            let n = frames as Fp;
+            self.step = target.zip_with(*coefs, |y, x| (y - x) / n);
+            self.target = target;
+            self.last = *coefs;
+            self.remain = frames;
+        }
+    }
+
+    fn tick(
+        &mut self,
+        coefs: &mut BiquadCoefs,
+    ) {
+        if self.is_active(coefs) {
+            self.remain -= 1;
+            *coefs = if self.remain == 0 {
+                self.target
+            } else {
+                coefs.zip_with(self.step, |x, d| x + d)
+            };
+            self.last = *coefs;
+        } else {
+            self.remain = 0;
+        }
+    }
+
+    fn is_active(
+        &self,
+        coefs: &BiquadCoefs,
+    ) -> bool {
+        self.remain > 0 && *coefs == self.last
+    }
+}
+
 /// 2nd order IIR filter implemented in normalized Direct form I.
+///
+/// Coefficients can glide to a target, linearly interpolated at each frame.
+/// If both the current and the target coefficients are stable, so is each
+/// set of coefficients in between, since the region of stable coefficients
+/// is convex.  This does not guarantee the stability of the time-varying
+/// filter, especially for fast glides at high Q.
+///
+/// To sweep the cutoff, compute coefficients at a control rate (e.g. with
+/// a [`Driver`](crate::sched::Driver)) and glide to them over the control
+/// period.
 #[derive(Debug)]
 pub struct Biquad<T> {
     pub coefs: BiquadCoefs,
+    glide:     CoefsGlide,
     x1:        T,
     x2:        T,
     y1:        T,
@@ -231,6 +747,7 @@ where
     pub fn new(coefs: BiquadCoefs) -> Self {
         Self {
             coefs,
+            glide: CoefsGlide::default(),
             x1: zero(),
             x2: zero(),
             y1: zero(),
@@ -245,6 +762,55 @@ where
     pub fn coeffs_mut(&mut self) -> &mut BiquadCoefs {
         &mut self.coefs
     }
+
+    /// Glide to `target` over `frames` frames.
+    ///
+    /// Assigning coefficients directly cancels the glide.
+    pub fn glide(
+        &mut self,
+        target: BiquadCoefs,
+        frames: usize,
+    ) {
+        self.glide.start(&mut self.coefs, target, frames);
+    }
+
+    #[must_use]
+    pub fn is_gliding(&self) -> bool {
+        self.glide.is_active(&self.coefs)
+    }
+
+    fn ctl(&mut self) -> BiquadCtl<'_, T> {
+        BiquadCtl {
+            coefs: &mut self.coefs,
+            glide: &mut self.glide,
+            x1:    &mut self.x1,
+            x2:    &mut self.x2,
+            y1:    &mut self.y1,
+            y2:    &mut self.y2,
+        }
+    }
+}
+
+impl Response for BiquadCoefs {
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        let z1 = Complex::cis(-<Fp as Real>::TAU * freq);
+        let z2 = z1 * z1;
+        let num = Complex::from(self.b0) + z1 * self.b1 + z2 * self.b2;
+        let den = Complex::from(1.) + z1 * self.a1 + z2 * self.a2;
+        num / den
+    }
+}
+
+impl<T> Response for Biquad<T> {
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.coefs.response(freq)
+    }
 }
 
 impl<T> Default for Biquad<T>
@@ -276,6 +842,7 @@ where
         frames: &mut [Self::Frame],
     ) {
         for frm in frames {
+            self.glide.tick(&mut self.coefs);
             let b0 = self.coefs.b0.to_float();
             let b1 = self.coefs.b1.to_float();
             let b2 = self.coefs.b2.to_float();
@@ -293,6 +860,79 @@ where
             *frm = y0;
         }
     }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+
+    /// Visit the coefficients.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.param("a1", &mut self.coefs.a1);
+        visitor.param("a2", &mut self.coefs.a2);
+        visitor.param("b0", &mut self.coefs.b0);
+        visitor.param("b1", &mut self.coefs.b1);
+        visitor.param("b2", &mut self.coefs.b2);
+    }
+}
+
+/// Handle to coefficients and state of [`Biquad`].
+///
+/// Reset clears the filter history.
+#[derive(Debug)]
+pub struct BiquadCtl<'a, T> {
+    pub coefs: &'a mut BiquadCoefs,
+    glide:     &'a mut CoefsGlide,
+    x1:        &'a mut T,
+    x2:        &'a mut T,
+    y1:        &'a mut T,
+    y2:        &'a mut T,
+}
+
+impl<'a, T> BiquadCtl<'a, T> {
+    /// Glide to `target` over `frames` frames.
+    ///
+    /// See [`Biquad::glide`].
+    pub fn glide(
+        &mut self,
+        target: BiquadCoefs,
+        frames: usize,
+    ) {
+        self.glide.start(self.coefs, target, frames);
+    }
+
+    #[must_use]
+    pub fn is_gliding(&self) -> bool {
+        self.glide.is_active(self.coefs)
+    }
+}
+
+impl<'a, T> Reset for BiquadCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.x1 = zero();
+        *self.x2 = zero();
+        *self.y1 = zero();
+        *self.y2 = zero();
+    }
+}
+
+impl<T> Control for Biquad<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = BiquadCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
 }
 
 #[must_use]
@@ -313,66 +953,77 @@ pub fn resonator<T: Frame>(
     Biquad::new(BiquadCoefs::resonator(center, bandwidth))
 }
 
-/// Nth order Butterworth lowpass filter
-#[derive(Debug)]
-pub struct Lpf<const N: usize, T> {
-    filters: [Biquad<T>; N],
+#[must_use]
+pub fn lowpass<T: Frame>(
+    cutoff: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::lowpass(cutoff, q))
 }
 
-impl<const N: usize, T> Lpf<N, T>
-where
-    T: Frame,
-{
-    #[must_use]
-    pub fn new(cutoff: Fp) -> Self {
-        Self {
-            filters: [0; N].map(|_| butter_lowpass(cutoff)),
-        }
-    }
+#[must_use]
+pub fn hipass<T: Frame>(
+    cutoff: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::hipass(cutoff, q))
 }
 
-impl<const N: usize, T> From<Fp> for Lpf<N, T>
-where
-    T: Frame,
-{
-    fn from(value: Fp) -> Self {
-        Self::new(value)
-    }
+#[must_use]
+pub fn bandpass<T: Frame>(
+    center: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::bandpass(center, q))
 }
 
-impl<const N: usize, T> Node for Lpf<N, T>
-where
-    T: Frame,
-{
-    type Frame = T;
+#[must_use]
+pub fn bandpass_peak<T: Frame>(
+    center: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::bandpass_peak(center, q))
+}
 
-    fn proc(
-        &mut self,
-        frames: &mut [Self::Frame],
-    ) {
-        self.filters
-            .as_mut_slice()
-            .iter_mut()
-            .for_each(|f| f.proc(frames));
-    }
+#[must_use]
+pub fn notch<T: Frame>(
+    center: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::notch(center, q))
 }
 
 #[must_use]
-pub fn lpf<const N: usize, T: Frame>(cutoff: Fp) -> Lpf<N, T> {
-    Lpf::new(cutoff)
+pub fn allpass<T: Frame>(
+    center: Fp,
+    q: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::allpass(center, q))
 }
 
 #[must_use]
-pub fn lpf2<T: Frame>(cutoff: Fp) -> Lpf<2, T> {
-    Lpf::new(cutoff)
+pub fn peak<T: Frame>(
+    center: Fp,
+    q: Fp,
+    gain: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::peak(center, q, gain))
 }
 
 #[must_use]
-pub fn lpf4<T: Frame>(cutoff: Fp) -> Lpf<4, T> {
-    Lpf::new(cutoff)
+pub fn lowshelf<T: Frame>(
+    cutoff: Fp,
+    q: Fp,
+    gain: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::lowshelf(cutoff, q, gain))
 }
 
 #[must_use]
-pub fn lpf8<T: Frame>(cutoff: Fp) -> Lpf<8, T> {
-    Lpf::new(cutoff)
+pub fn hishelf<T: Frame>(
+    cutoff: Fp,
+    q: Fp,
+    gain: Fp,
+) -> Biquad<T> {
+    Biquad::new(BiquadCoefs::hishelf(cutoff, q, gain))
 }
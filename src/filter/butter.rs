@@ -0,0 +1,478 @@
+use std::marker::PhantomData;
+
+use super::{
+    Biquad,
+    BiquadCoefs,
+    Response,
+};
+use crate::{
+    context::Context,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
+    frame::Frame,
+    node::Node,
+    num::{
+        Complex,
+        Fp,
+        Real,
+    },
+};
+
+impl BiquadCoefs {
+    /// Returns settings for `N` sections of a Butterworth lowpass filter of
+    /// order `2 * N`.  Cutoff is the -3 dB point of the whole cascade.
+    #[must_use]
+    pub fn butter_lowpass_cascade<const N: usize>(cutoff: Fp) -> [Self; N] {
+        std::array::from_fn(|k| Self::lowpass(cutoff, butter_q(k, N)))
+    }
+
+    /// Returns settings for `N` sections of a Butterworth hipass filter of
+    /// order `2 * N`.  Cutoff is the -3 dB point of the whole cascade.
+    #[must_use]
+    pub fn butter_hipass_cascade<const N: usize>(cutoff: Fp) -> [Self; N] {
+        std::array::from_fn(|k| Self::hipass(cutoff, butter_q(k, N)))
+    }
+
+    /// Returns settings for `N` sections of a Butterworth bandpass filter of
+    /// order `2 * N`, designed from the lowpass prototype of order `N`.
+    ///
+    /// `low` and `high` are the -3 dB points of the whole cascade.  The gain
+    /// is unity at their geometric mean, after prewarping.
+    #[must_use]
+    pub fn butter_bandpass_cascade<const N: usize>(
+        low: Fp,
+        high: Fp,
+    ) -> [Self; N] {
+        let wl = <Fp as Real>::tan(<Fp as Real>::PI * low);
+        let wh = <Fp as Real>::tan(<Fp as Real>::PI * high);
+        let w0_sqr = wl * wh;
+        let bw = wh - wl;
+
+        std::array::from_fn(|k| {
+            // Each pole `p` of the prototype in the upper half-plane maps to
+            // two poles, roots of `s^2 - p * bw * s + w0^2`.  Together with
+            // their conjugates, they make two sections.  For odd `N`, the
+            // real pole of the prototype makes the last section, `s^2 + bw *
+            // s + w0^2`, whose roots are real for wide bands.
+            let (a0, a1) = if k < N / 2 * 2 {
+                #[allow(clippy::cast_precision_loss)]
+                let theta =
+                    (2 * (k / 2) + 1) as Fp * <Fp as Real>::PI / (2 * N) as Fp;
+                let p = Complex::new(-theta.sin(), theta.cos()) * bw;
+                let d = (p * p - Complex::from(4. * w0_sqr)).sqrt();
+                let pole = if k % 2 == 0 {
+                    (p + d) * 0.5
+                } else {
+                    (p - d) * 0.5
+                };
+                (pole.norm_sqr(), -2. * pole.re)
+            } else {
+                (w0_sqr, bw)
+            };
+            // unity gain at the center
+            let center = Complex::new(0., w0_sqr.sqrt());
+            let gain = (center
+                / (center * center + center * a1 + Complex::from(a0)))
+            .norm();
+            Self::bilinear([0., gain.recip(), 0.], [a0, a1, 1.])
+        })
+    }
+
+    // Map the analog section `num(s) / den(s)`, coefficients in ascending
+    // powers of `s`, with the bilinear transform `s = (1 - z^-1) / (1 +
+    // z^-1)`.  Frequencies of the section must be prewarped with
+    // `tan(PI * f)`.
+    pub(super) fn bilinear(
+        num: [Fp; 3],
+        den: [Fp; 3],
+    ) -> Self {
+        Self::cookbook(
+            num[0] + num[1] + num[2],
+            2.0 * (num[0] - num[2]),
+            num[0] - num[1] + num[2],
+            den[0] + den[1] + den[2],
+            2.0 * (den[0] - den[2]),
+            den[0] - den[1] + den[2],
+        )
+    }
+}
+
+// Resonance of the section `k` of a Butterworth filter of order `2 * n`.
+#[allow(clippy::cast_precision_loss)]
+pub(super) fn butter_q(
+    k: usize,
+    n: usize,
+) -> Fp {
+    let theta = (2 * k + 1) as Fp * <Fp as Real>::PI / (4 * n) as Fp;
+    1. / (2. * theta.cos())
+}
+
+/// Design of the sections of a Butterworth cascade.
+pub trait Design {
+    /// Parameters of the design, as fractions of the sample rate.
+    type Params: Copy;
+
+    /// Returns settings for `N` sections.
+    fn design<const N: usize>(params: Self::Params) -> [BiquadCoefs; N];
+}
+
+/// Lowpass design, parameterised by its cutoff.
+#[derive(Debug, Clone, Copy)]
+pub struct Lowpass;
+
+impl Design for Lowpass {
+    type Params = Fp;
+
+    fn design<const N: usize>(cutoff: Fp) -> [BiquadCoefs; N] {
+        BiquadCoefs::butter_lowpass_cascade(cutoff)
+    }
+}
+
+/// Hipass design, parameterised by its cutoff.
+#[derive(Debug, Clone, Copy)]
+pub struct Hipass;
+
+impl Design for Hipass {
+    type Params = Fp;
+
+    fn design<const N: usize>(cutoff: Fp) -> [BiquadCoefs; N] {
+        BiquadCoefs::butter_hipass_cascade(cutoff)
+    }
+}
+
+/// Bandpass design, parameterised by its band edges `(low, high)`.
+#[derive(Debug, Clone, Copy)]
+pub struct Bandpass;
+
+impl Design for Bandpass {
+    type Params = (Fp, Fp);
+
+    fn design<const N: usize>((low, high): (Fp, Fp)) -> [BiquadCoefs; N] {
+        BiquadCoefs::butter_bandpass_cascade(low, high)
+    }
+}
+
+/// Butterworth filter of order `2 * N`, made of `N` biquad sections
+/// designed by `D`.
+#[derive(Debug)]
+pub struct Butter<const N: usize, T, D> {
+    filters: [Biquad<T>; N],
+    _design: PhantomData<D>,
+}
+
+/// Butterworth lowpass filter of order `2 * N`.
+pub type Lpf<const N: usize, T> = Butter<N, T, Lowpass>;
+
+/// Butterworth hipass filter of order `2 * N`.
+pub type Hpf<const N: usize, T> = Butter<N, T, Hipass>;
+
+/// Butterworth bandpass filter of order `2 * N`.
+///
+/// The band edges `low` and `high` are the -3 dB points.
+pub type Bpf<const N: usize, T> = Butter<N, T, Bandpass>;
+
+impl<const N: usize, T, D> Butter<N, T, D>
+where
+    T: Frame,
+    D: Design,
+{
+    #[must_use]
+    pub fn with_params(params: D::Params) -> Self {
+        Self {
+            filters: D::design(params).map(Biquad::new),
+            _design: PhantomData,
+        }
+    }
+}
+
+impl<const N: usize, T, D> Butter<N, T, D>
+where
+    T: Frame,
+    D: Design<Params = Fp>,
+{
+    #[must_use]
+    pub fn new(cutoff: Fp) -> Self {
+        Self::with_params(cutoff)
+    }
+
+    /// Filter with cutoff in Hz.
+    #[must_use]
+    pub fn hz(
+        cutoff: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(ctx.hz(cutoff))
+    }
+}
+
+impl<const N: usize, T> Butter<N, T, Bandpass>
+where
+    T: Frame,
+{
+    #[must_use]
+    pub fn new(
+        low: Fp,
+        high: Fp,
+    ) -> Self {
+        Self::with_params((low, high))
+    }
+
+    /// Bandpass filter with band edges in Hz.
+    #[must_use]
+    pub fn hz(
+        low: Fp,
+        high: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(ctx.hz(low), ctx.hz(high))
+    }
+}
+
+/// Product of responses of all sections.
+impl<const N: usize, T, D> Response for Butter<N, T, D> {
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.filters
+            .iter()
+            .fold(Complex::from(1.), |acc, f| acc * f.response(freq))
+    }
+}
+
+impl<const N: usize, T, D> From<Fp> for Butter<N, T, D>
+where
+    T: Frame,
+    D: Design<Params = Fp>,
+{
+    fn from(value: Fp) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize, T, D> Node for Butter<N, T, D>
+where
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        self.filters.iter_mut().for_each(|f| f.proc(frames));
+    }
+
+    fn reset_state(&mut self) {
+        for f in &mut self.filters {
+            f.reset_state();
+        }
+    }
+
+    /// Visit the coefficients of each section.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        for (i, f) in self.filters.iter_mut().enumerate() {
+            visitor.enter(i);
+            f.visit_params(visitor);
+            visitor.leave();
+        }
+    }
+}
+
+/// Handle to the sections of [`Butter`].
+///
+/// Reset clears the history of all sections.
+#[derive(Debug)]
+pub struct ButterCtl<'a, const N: usize, T, D> {
+    filters: &'a mut [Biquad<T>; N],
+    _design: PhantomData<D>,
+}
+
+pub type LpfCtl<'a, const N: usize, T> = ButterCtl<'a, N, T, Lowpass>;
+pub type HpfCtl<'a, const N: usize, T> = ButterCtl<'a, N, T, Hipass>;
+pub type BpfCtl<'a, const N: usize, T> = ButterCtl<'a, N, T, Bandpass>;
+
+impl<'a, const N: usize, T, D> ButterCtl<'a, N, T, D>
+where
+    T: Frame,
+    D: Design,
+{
+    /// Redesign all sections, keeping their state.
+    pub fn set_params(
+        &mut self,
+        params: D::Params,
+    ) {
+        for (f, c) in self.filters.iter_mut().zip(D::design::<N>(params)) {
+            f.coefs = c;
+        }
+    }
+
+    /// Glide all sections to a new design over `frames` frames.
+    pub fn glide_params(
+        &mut self,
+        params: D::Params,
+        frames: usize,
+    ) {
+        for (f, c) in self.filters.iter_mut().zip(D::design::<N>(params)) {
+            f.glide(c, frames);
+        }
+    }
+
+    pub fn filters(&mut self) -> &mut [Biquad<T>; N] {
+        self.filters
+    }
+}
+
+impl<'a, const N: usize, T, D> ButterCtl<'a, N, T, D>
+where
+    T: Frame,
+    D: Design<Params = Fp>,
+{
+    /// Set the cutoff of all sections, keeping their state.
+    pub fn set_cutoff(
+        &mut self,
+        cutoff: Fp,
+    ) {
+        self.set_params(cutoff);
+    }
+
+    /// Glide the cutoff of all sections over `frames` frames.
+    pub fn glide_cutoff(
+        &mut self,
+        cutoff: Fp,
+        frames: usize,
+    ) {
+        self.glide_params(cutoff, frames);
+    }
+}
+
+impl<'a, const N: usize, T> ButterCtl<'a, N, T, Bandpass>
+where
+    T: Frame,
+{
+    /// Set the band edges of all sections, keeping their state.
+    pub fn set_band(
+        &mut self,
+        low: Fp,
+        high: Fp,
+    ) {
+        self.set_params((low, high));
+    }
+
+    /// Glide the band edges of all sections over `frames` frames.
+    pub fn glide_band(
+        &mut self,
+        low: Fp,
+        high: Fp,
+        frames: usize,
+    ) {
+        self.glide_params((low, high), frames);
+    }
+}
+
+impl<'a, const N: usize, T, D> Reset for ButterCtl<'a, N, T, D>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        for f in self.filters.iter_mut() {
+            f.reset_state();
+        }
+    }
+}
+
+impl<const N: usize, T, D> Control for Butter<N, T, D>
+where
+    T: Frame + 'static,
+    D: 'static,
+{
+    type Ctl<'a> = ButterCtl<'a, N, T, D> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut ButterCtl {
+            filters: &mut self.filters,
+            _design: PhantomData,
+        });
+    }
+}
+
+#[must_use]
+pub fn lpf<const N: usize, T: Frame>(cutoff: Fp) -> Lpf<N, T> {
+    Lpf::new(cutoff)
+}
+
+#[must_use]
+pub fn lpf2<T: Frame>(cutoff: Fp) -> Lpf<2, T> {
+    Lpf::new(cutoff)
+}
+
+#[must_use]
+pub fn lpf4<T: Frame>(cutoff: Fp) -> Lpf<4, T> {
+    Lpf::new(cutoff)
+}
+
+#[must_use]
+pub fn lpf8<T: Frame>(cutoff: Fp) -> Lpf<8, T> {
+    Lpf::new(cutoff)
+}
+
+#[must_use]
+pub fn hpf<const N: usize, T: Frame>(cutoff: Fp) -> Hpf<N, T> {
+    Hpf::new(cutoff)
+}
+
+#[must_use]
+pub fn hpf2<T: Frame>(cutoff: Fp) -> Hpf<2, T> {
+    Hpf::new(cutoff)
+}
+
+#[must_use]
+pub fn hpf4<T: Frame>(cutoff: Fp) -> Hpf<4, T> {
+    Hpf::new(cutoff)
+}
+
+#[must_use]
+pub fn hpf8<T: Frame>(cutoff: Fp) -> Hpf<8, T> {
+    Hpf::new(cutoff)
+}
+
+#[must_use]
+pub fn bpf<const N: usize, T: Frame>(
+    low: Fp,
+    high: Fp,
+) -> Bpf<N, T> {
+    Bpf::new(low, high)
+}
+
+#[must_use]
+pub fn bpf2<T: Frame>(
+    low: Fp,
+    high: Fp,
+) -> Bpf<2, T> {
+    Bpf::new(low, high)
+}
+
+#[must_use]
+pub fn bpf4<T: Frame>(
+    low: Fp,
+    high: Fp,
+) -> Bpf<4, T> {
+    Bpf::new(low, high)
+}
+
+#[must_use]
+pub fn bpf8<T: Frame>(
+    low: Fp,
+    high: Fp,
+) -> Bpf<8, T> {
+    Bpf::new(low, high)
+}
@@ -0,0 +1,392 @@
+use std::alloc::{
+    Allocator,
+    Global,
+};
+
+use super::{
+    butter::butter_q,
+    Biquad,
+    BiquadCoefs,
+    Response,
+};
+use crate::{
+    control::Visit,
+    frame::Frame,
+    mix::Mix,
+    node::Node,
+    num::{
+        Complex,
+        Fp,
+        Real,
+    },
+};
+
+/// Order of a Linkwitz-Riley crossover.
+///
+/// A Linkwitz-Riley filter is a Butterworth filter of half the order,
+/// squared.  Lowpass and hipass outputs are -6 dB at the crossover frequency
+/// and sum to an allpass response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LrOrder {
+    /// 12 dB/oct.  The hipass output is inverted, so that the outputs sum
+    /// to an allpass.
+    Lr2,
+    /// 24 dB/oct.
+    #[default]
+    Lr4,
+    /// 48 dB/oct.
+    Lr8,
+}
+
+impl LrOrder {
+    // Number of biquad sections of lowpass and hipass.
+    fn sections(self) -> usize {
+        match self {
+            Self::Lr2 => 1,
+            Self::Lr4 => 2,
+            Self::Lr8 => 4,
+        }
+    }
+
+    fn lowpass(
+        self,
+        cutoff: Fp,
+    ) -> impl Iterator<Item = BiquadCoefs> {
+        let coefs = match self {
+            Self::Lr2 => [BiquadCoefs::lowpass(cutoff, 0.5); 2],
+            Self::Lr4 => [BiquadCoefs::butter_lowpass(cutoff); 2],
+            Self::Lr8 => BiquadCoefs::butter_lowpass_cascade::<2>(cutoff),
+        };
+        coefs.into_iter().cycle().take(self.sections())
+    }
+
+    fn hipass(
+        self,
+        cutoff: Fp,
+    ) -> impl Iterator<Item = BiquadCoefs> {
+        let coefs = match self {
+            Self::Lr2 => [BiquadCoefs::hipass(cutoff, 0.5); 2],
+            Self::Lr4 => [BiquadCoefs::butter_hipass(cutoff); 2],
+            Self::Lr8 => BiquadCoefs::butter_hipass_cascade::<2>(cutoff),
+        };
+        coefs.into_iter().cycle().take(self.sections())
+    }
+
+    // Sections of the sum of lowpass and hipass outputs.
+    fn allpass(
+        self,
+        cutoff: Fp,
+    ) -> impl Iterator<Item = BiquadCoefs> {
+        let (coefs, len) = match self {
+            Self::Lr2 => {
+                // first order `(1 - s) / (1 + s)`
+                let w = <Fp as Real>::tan(<Fp as Real>::PI * cutoff);
+                let w2 = w * w;
+                let ap =
+                    BiquadCoefs::bilinear([w2, 0.0, -1.0], [w2, 2.0 * w, 1.0]);
+                ([ap; 2], 1)
+            }
+            Self::Lr4 => ([BiquadCoefs::allpass(cutoff, butter_q(0, 1)); 2], 1),
+            Self::Lr8 => (
+                [0, 1].map(|k| BiquadCoefs::allpass(cutoff, butter_q(k, 2))),
+                2,
+            ),
+        };
+        coefs.into_iter().take(len)
+    }
+}
+
+/// One band of a [`Crossover`].
+///
+/// Hipass sections of the crossover frequencies below the band, lowpass
+/// sections of the frequency above it, and allpass sections matching the
+/// phase of the crossover frequencies further above.
+pub struct LrBand<T, A = Global>
+where
+    A: Allocator,
+{
+    filters: Vec<Biquad<T>, A>,
+    // -1 for odd bands of LR2
+    sign:    Fp,
+}
+
+impl<T, A> LrBand<T, A>
+where
+    A: Allocator,
+{
+    /// Biquad sections of the band.
+    pub fn as_slice(&self) -> &[Biquad<T>] {
+        &self.filters
+    }
+
+    /// Whether the output of the band is inverted.
+    #[must_use]
+    pub fn is_inverted(&self) -> bool {
+        self.sign < 0.
+    }
+}
+
+impl<T, A> Response for LrBand<T, A>
+where
+    A: Allocator,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.filters
+            .iter()
+            .fold(Complex::from(self.sign), |acc, f| acc * f.response(freq))
+    }
+}
+
+impl<T, A> Node for LrBand<T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        for f in self.filters.iter_mut() {
+            f.proc(frames);
+        }
+        if self.sign < 0. {
+            for frm in frames {
+                *frm = -*frm;
+            }
+        }
+    }
+
+    fn reset_state(&mut self) {
+        for f in self.filters.iter_mut() {
+            f.reset_state();
+        }
+    }
+
+    /// Visit the coefficients of each section.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        for (i, f) in self.filters.iter_mut().enumerate() {
+            visitor.enter(i);
+            f.visit_params(visitor);
+            visitor.leave();
+        }
+    }
+}
+
+impl<T, A> std::fmt::Debug for LrBand<T, A>
+where
+    A: Allocator,
+    T: std::fmt::Debug,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("LrBand")
+            .field("filters", &self.filters)
+            .field("sign", &self.sign)
+            .finish()
+    }
+}
+
+/// Linkwitz-Riley crossover splitting the signal into bands.
+///
+/// With `n` crossover frequencies, there are `n + 1` bands, from the lowest
+/// one.  The bands sum to an allpass-flat signal: the magnitude response of
+/// the sum is flat, and its phase is that of the allpass sections of all
+/// crossover frequencies.
+///
+/// Process all bands at once with [`proc_bands`](Self::proc_bands), or
+/// route them in parallel with [`into_mix`](Self::into_mix) and
+/// [`into_bands`](Self::into_bands).
+pub struct Crossover<T, A = Global>
+where
+    A: Allocator,
+{
+    bands: Vec<LrBand<T, A>, A>,
+    order: LrOrder,
+}
+
+impl<T, A> Crossover<T, A>
+where
+    A: Allocator + Clone,
+    T: Frame,
+{
+    /// Crossover of `order` at frequencies `freqs`, as fractions of the
+    /// sample rate.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `freqs` are not in increasing order.
+    pub fn new_in(
+        order: LrOrder,
+        freqs: &[Fp],
+        alloc: A,
+    ) -> Self {
+        assert!(
+            freqs.windows(2).all(|w| w[0] < w[1]),
+            "crossover frequencies must be increasing"
+        );
+        let mut bands = Vec::with_capacity_in(freqs.len() + 1, alloc.clone());
+        for k in 0..=freqs.len() {
+            let mut filters = Vec::new_in(alloc.clone());
+            for &freq in &freqs[..k] {
+                filters.extend(order.hipass(freq).map(Biquad::new));
+            }
+            if let Some(&freq) = freqs.get(k) {
+                filters.extend(order.lowpass(freq).map(Biquad::new));
+            }
+            for &freq in freqs.iter().skip(k + 1) {
+                filters.extend(order.allpass(freq).map(Biquad::new));
+            }
+            let sign = if order == LrOrder::Lr2 && k % 2 == 1 {
+                -1.
+            } else {
+                1.
+            };
+            bands.push(LrBand {
+                filters,
+                sign,
+            });
+        }
+        Self {
+            bands,
+            order,
+        }
+    }
+}
+
+impl<T, A> Crossover<T, A>
+where
+    A: Allocator,
+{
+    #[must_use]
+    pub fn order(&self) -> LrOrder {
+        self.order
+    }
+
+    /// Number of bands.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Always false: there is at least one band.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.bands.is_empty()
+    }
+
+    pub fn as_slice(&self) -> &[LrBand<T, A>] {
+        &self.bands
+    }
+
+    pub fn as_mut_slice(&mut self) -> &mut [LrBand<T, A>] {
+        &mut self.bands
+    }
+
+    /// Bands as separate nodes, from the lowest one.
+    pub fn into_bands(self) -> Vec<LrBand<T, A>, A> {
+        self.bands
+    }
+
+    /// Process `input` into all bands at once.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of `outputs` differs from the number of bands,
+    /// or if any of them is shorter than `input`.
+    pub fn proc_bands(
+        &mut self,
+        input: &[T],
+        outputs: &mut [&mut [T]],
+    ) where
+        T: Frame,
+    {
+        assert_eq!(outputs.len(), self.bands.len(), "number of bands");
+        for (band, output) in self.bands.iter_mut().zip(outputs) {
+            let output = &mut output[..input.len()];
+            output.copy_from_slice(input);
+            band.proc(output);
+        }
+    }
+
+    pub fn reset_state(&mut self)
+    where
+        T: Frame,
+    {
+        for band in self.bands.iter_mut() {
+            band.reset_state();
+        }
+    }
+}
+
+impl<T, A> Crossover<T, A>
+where
+    A: Allocator + Clone + Sync + Send,
+    T: Frame + Sync + Send,
+{
+    /// Mix of all bands, each in its own branch with unity gain.
+    ///
+    /// Branch gains and [`Mix::replace`] then act per band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size == 0`.
+    pub fn into_mix<'a>(
+        self,
+        size: usize,
+    ) -> Mix<'a, T, A>
+    where
+        A: 'a,
+        T: 'a,
+    {
+        let alloc = self.bands.allocator().clone();
+        let mut mix = Mix::new_in(size, alloc.clone());
+        for band in self.bands {
+            mix.node_push_in(band, alloc.clone());
+        }
+        mix
+    }
+}
+
+impl<T> Crossover<T>
+where
+    T: Frame,
+{
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `freqs` are not in increasing order.
+    #[must_use]
+    pub fn new(
+        order: LrOrder,
+        freqs: &[Fp],
+    ) -> Self {
+        Self::new_in(order, freqs, Global)
+    }
+}
+
+impl<T, A> std::fmt::Debug for Crossover<T, A>
+where
+    A: Allocator,
+    T: std::fmt::Debug,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Crossover")
+            .field("bands", &self.bands)
+            .field("order", &self.order)
+            .finish()
+    }
+}
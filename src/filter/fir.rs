@@ -0,0 +1,314 @@
+use std::alloc::{
+    Allocator,
+    Global,
+};
+
+use super::Response;
+use crate::{
+    frame::Frame,
+    node::Node,
+    num::{
+        zero,
+        Complex,
+        Float,
+        Fp,
+        Real,
+    },
+    window::Window,
+    Buf,
+};
+
+/// Response of a windowed-sinc kernel.  Frequencies are fractions of the
+/// sample rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FirKind {
+    Lowpass(Fp),
+    Hipass(Fp),
+    /// Band between `low` and `high`.
+    Bandpass(Fp, Fp),
+    /// Rejects the band between `low` and `high`.
+    Bandstop(Fp, Fp),
+}
+
+/// Fill `taps` with a linear-phase windowed-sinc kernel.
+///
+/// The ideal response is truncated to `taps.len()` points around its center
+/// and shaped by `window`.  Lowpass kernels have unity gain at DC; hipass,
+/// bandpass and bandstop are derived from them by spectral inversion, so
+/// their gain is unity in the middle of the pass band.
+///
+/// # Panics
+///
+/// Panics if `taps` is empty, or if its length is even for hipass and
+/// bandstop, which would need a zero at Nyquist.
+pub fn design_fir(
+    kind: FirKind,
+    window: Window,
+    taps: &mut [Fp],
+) {
+    assert!(!taps.is_empty(), "kernel must not be empty");
+    taps.fill(0.);
+    match kind {
+        FirKind::Lowpass(cutoff) => add_lowpass(cutoff, window, taps, 1.),
+        FirKind::Hipass(cutoff) => {
+            add_lowpass(cutoff, window, taps, -1.);
+            add_impulse(taps);
+        }
+        FirKind::Bandpass(low, high) => {
+            add_lowpass(high, window, taps, 1.);
+            add_lowpass(low, window, taps, -1.);
+        }
+        FirKind::Bandstop(low, high) => {
+            add_lowpass(low, window, taps, 1.);
+            add_lowpass(high, window, taps, -1.);
+            add_impulse(taps);
+        }
+    }
+}
+
+// Add windowed sinc normalized to `gain` at DC.  The kernel is computed
+// twice, to normalize it without allocating.
+fn add_lowpass(
+    cutoff: Fp,
+    window: Window,
+    taps: &mut [Fp],
+    gain: Fp,
+) {
+    let len = taps.len();
+    #[allow(clippy::cast_precision_loss)]
+    let center = (len - 1) as Fp * 0.5;
+    let value = |n: usize| {
+        #[allow(clippy::cast_precision_loss)]
+        let t = n as Fp - center;
+        let sinc = if t == 0. {
+            2. * cutoff
+        } else {
+            <Fp as Real>::sin(<Fp as Real>::TAU * cutoff * t)
+                / (<Fp as Real>::PI * t)
+        };
+        sinc * window.value(n, len)
+    };
+    let scale = gain / (0..len).map(value).sum::<Fp>();
+    for (n, x) in taps.iter_mut().enumerate() {
+        *x += value(n) * scale;
+    }
+}
+
+// Add a unit impulse at the center of the kernel.
+fn add_impulse(taps: &mut [Fp]) {
+    let len = taps.len();
+    assert!(len % 2 == 1, "kernel length must be odd");
+    taps[len / 2] += 1.;
+}
+
+/// Direct-form FIR filter.
+///
+/// Taps and history are kept in [`Buf`]s allocated up front.  The history
+/// holds every frame twice, so that the convolution runs over a contiguous
+/// slice.
+#[derive(Debug)]
+pub struct Fir<T, A = Global>
+where
+    A: Allocator,
+{
+    taps:    Buf<Fp, A>,
+    history: Buf<T, A>,
+    pos:     usize,
+    latency: usize,
+}
+
+impl<T, A> Fir<T, A>
+where
+    A: Allocator + Clone,
+    T: Frame,
+{
+    /// Filter with a copy of `taps`.  Reports no latency.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps` is empty.
+    pub fn new_in(
+        taps: &[Fp],
+        alloc: A,
+    ) -> Self {
+        let mut fir = Self::alloc_in(taps.len(), alloc);
+        fir.taps.as_mut_slice().copy_from_slice(taps);
+        fir
+    }
+
+    /// Filter with a copy of symmetric `taps`.  Reports the latency of
+    /// `(taps.len() - 1) / 2` frames, the delay of the center of the kernel.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps` is not symmetric, or if its length is even, which
+    /// would put the center between two frames.
+    pub fn linear_phase_in(
+        taps: &[Fp],
+        alloc: A,
+    ) -> Self {
+        assert!(
+            taps.iter().eq(taps.iter().rev()),
+            "linear-phase kernel must be symmetric"
+        );
+        assert!(
+            taps.len() % 2 == 1,
+            "linear-phase kernel length must be odd"
+        );
+        let mut fir = Self::new_in(taps, alloc);
+        fir.latency = (taps.len() - 1) / 2;
+        fir
+    }
+
+    /// Linear-phase filter with a windowed-sinc kernel of `len` taps.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is even, as in
+    /// [`linear_phase_in`](Self::linear_phase_in).  See also [`design_fir`].
+    pub fn design_in(
+        kind: FirKind,
+        window: Window,
+        len: usize,
+        alloc: A,
+    ) -> Self {
+        assert!(len % 2 == 1, "linear-phase kernel length must be odd");
+        let mut fir = Self::alloc_in(len, alloc);
+        design_fir(kind, window, fir.taps.as_mut_slice());
+        fir.latency = (len - 1) / 2;
+        fir
+    }
+
+    fn alloc_in(
+        len: usize,
+        alloc: A,
+    ) -> Self {
+        assert!(len > 0, "kernel must not be empty");
+        Self {
+            taps:    Buf::alloc_new_in(len, alloc.clone()),
+            history: Buf::alloc_new_in(2 * len, alloc),
+            pos:     0,
+            latency: 0,
+        }
+    }
+}
+
+impl<T, A> Fir<T, A>
+where
+    A: Allocator,
+{
+    /// Number of taps.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.taps.len()
+    }
+
+    /// Always false: there is at least one tap.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.taps.is_empty()
+    }
+
+    /// Coefficients of the kernel, in order of delay.
+    pub fn taps(&self) -> &[Fp] {
+        self.taps.as_slice()
+    }
+
+    /// Change the taps in place, keeping the history.  The reported latency
+    /// is not changed.
+    pub fn taps_mut(&mut self) -> &mut [Fp] {
+        self.taps.as_mut_slice()
+    }
+}
+
+impl<T> Fir<T>
+where
+    T: Frame,
+{
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `taps` is empty.
+    #[must_use]
+    pub fn new(taps: &[Fp]) -> Self {
+        Self::new_in(taps, Global)
+    }
+
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// See [`linear_phase_in`](Self::linear_phase_in).
+    #[must_use]
+    pub fn linear_phase(taps: &[Fp]) -> Self {
+        Self::linear_phase_in(taps, Global)
+    }
+
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// See [`design_in`](Self::design_in).
+    #[must_use]
+    pub fn design(
+        kind: FirKind,
+        window: Window,
+        len: usize,
+    ) -> Self {
+        Self::design_in(kind, window, len, Global)
+    }
+}
+
+impl<T, A> Response for Fir<T, A>
+where
+    A: Allocator,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        let z1 = Complex::cis(-<Fp as Real>::TAU * freq);
+        // Horner's scheme in `z^-1`
+        self.taps
+            .iter()
+            .rev()
+            .fold(Complex::from(0.), |acc, h| acc * z1 + Complex::from(*h))
+    }
+}
+
+impl<T, A> Node for Fir<T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let len = self.taps.len();
+        for frm in frames {
+            self.pos = if self.pos == 0 { len - 1 } else { self.pos - 1 };
+            self.history[self.pos] = *frm;
+            self.history[self.pos + len] = *frm;
+
+            let window = &self.history.as_slice()[self.pos..self.pos + len];
+            let mut out: T = zero();
+            for (h, x) in self.taps.iter().zip(window) {
+                out += *x * h.to_float();
+            }
+            *frm = out;
+        }
+    }
+
+    fn latency(&self) -> usize {
+        self.latency
+    }
+
+    fn reset_state(&mut self) {
+        self.history.zero();
+        self.pos = 0;
+    }
+}
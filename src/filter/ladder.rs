@@ -0,0 +1,256 @@
+use crate::{
+    context::Context,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
+    frame::Frame,
+    node::Node,
+    num::{
+        zero,
+        Float,
+        Fp,
+        Real,
+    },
+};
+
+/// Output slope of [`Ladder`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LadderSlope {
+    /// Output of the second stage, 12 dB/oct.
+    Db12,
+    /// Output of the fourth stage, 24 dB/oct.
+    #[default]
+    Db24,
+}
+
+/// Moog-style 4-pole ladder lowpass with `tanh` saturation in the stages.
+///
+/// Each stage is a one-pole lowpass discretized with the topology-preserving
+/// transform.  The feedback loop is solved for its linear part without a
+/// unit delay, and the input of every stage is saturated by `tanh`, which
+/// keeps the filter bounded even when it self-oscillates.
+///
+/// Resonance of 1 is the edge of self-oscillation; above it, the filter
+/// oscillates at the cutoff frequency on its own.  As in the analog
+/// circuit, resonance attenuates the passband by `1 + 4 * resonance`.
+#[derive(Debug, Clone)]
+pub struct Ladder<T> {
+    pub slope:     LadderSlope,
+    /// Feedback amount, 0 to about 1.2.
+    pub resonance: Fp,
+    /// Input gain.  Higher drive saturates the stages more.
+    pub drive:     Fp,
+    cutoff:        Fp,
+    // gain of each one-pole stage
+    g:             Fp,
+    state:         [T; 4],
+}
+
+impl<T> Ladder<T>
+where
+    T: Frame,
+    T::Sample: Real,
+{
+    /// Ladder with `cutoff` as a fraction of the sample rate, unity drive
+    /// and 24 dB/oct slope.
+    #[must_use]
+    pub fn new(
+        cutoff: Fp,
+        resonance: Fp,
+    ) -> Self {
+        Self {
+            slope: LadderSlope::default(),
+            resonance,
+            drive: 1.,
+            cutoff,
+            g: Self::stage_gain(cutoff),
+            state: [zero(); 4],
+        }
+    }
+
+    /// Ladder with cutoff in Hz.
+    #[must_use]
+    pub fn hz(
+        cutoff: Fp,
+        resonance: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(ctx.hz(cutoff), resonance)
+    }
+
+    fn stage_gain(cutoff: Fp) -> Fp {
+        let g = <Fp as Real>::tan(<Fp as Real>::PI * cutoff);
+        g / (1. + g)
+    }
+
+    #[must_use]
+    pub fn cutoff(&self) -> Fp {
+        self.cutoff
+    }
+
+    pub fn set_cutoff(
+        &mut self,
+        cutoff: Fp,
+    ) {
+        self.cutoff = cutoff;
+        self.g = Self::stage_gain(cutoff);
+    }
+
+    /// Process one frame.
+    pub fn tick(
+        &mut self,
+        frame: T,
+    ) -> T {
+        let g = self.g;
+        let k = 4. * self.resonance;
+
+        // response of the linear cascade to its current state
+        let mut sigma = T::zero();
+        for s in &self.state {
+            sigma = sigma * g.to_float() + *s * (1. - g).to_float();
+        }
+        let input = frame * self.drive.to_float();
+        let fbk = (input - sigma * k.to_float())
+            * (1. + k * g * g * g * g).recip().to_float();
+
+        let mut stage = fbk;
+        let mut taps = [T::zero(); 4];
+        for (s, tap) in self.state.iter_mut().zip(&mut taps) {
+            let v = (tanh(stage) - *s) * g.to_float();
+            let y = v + *s;
+            *s = y + v;
+            *tap = y;
+            stage = y;
+        }
+        match self.slope {
+            LadderSlope::Db12 => taps[1],
+            LadderSlope::Db24 => taps[3],
+        }
+    }
+
+    fn ctl(&mut self) -> LadderCtl<'_, T> {
+        LadderCtl {
+            slope:     &mut self.slope,
+            resonance: &mut self.resonance,
+            drive:     &mut self.drive,
+            cutoff:    &mut self.cutoff,
+            g:         &mut self.g,
+            state:     &mut self.state,
+        }
+    }
+}
+
+fn tanh<T>(mut frame: T) -> T
+where
+    T: Frame,
+    T::Sample: Real,
+{
+    for x in frame.as_mut_slice() {
+        *x = x.tanh();
+    }
+    frame
+}
+
+impl<T> Node for Ladder<T>
+where
+    T: Frame,
+    T::Sample: Real,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        for frm in frames {
+            *frm = self.tick(*frm);
+        }
+    }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        let mut cutoff = self.cutoff;
+        visitor.param("cutoff", &mut cutoff);
+        if cutoff != self.cutoff {
+            self.set_cutoff(cutoff);
+        }
+        visitor.param("resonance", &mut self.resonance);
+        visitor.param("drive", &mut self.drive);
+    }
+}
+
+/// Handle to parameters and state of [`Ladder`].
+///
+/// Reset clears the state of the stages.
+#[derive(Debug)]
+pub struct LadderCtl<'a, T> {
+    pub slope:     &'a mut LadderSlope,
+    pub resonance: &'a mut Fp,
+    pub drive:     &'a mut Fp,
+    cutoff:        &'a mut Fp,
+    g:             &'a mut Fp,
+    state:         &'a mut [T; 4],
+}
+
+impl<'a, T> LadderCtl<'a, T>
+where
+    T: Frame,
+    T::Sample: Real,
+{
+    #[must_use]
+    pub fn cutoff(&self) -> Fp {
+        *self.cutoff
+    }
+
+    pub fn set_cutoff(
+        &mut self,
+        cutoff: Fp,
+    ) {
+        *self.cutoff = cutoff;
+        *self.g = Ladder::<T>::stage_gain(cutoff);
+    }
+}
+
+impl<'a, T> Reset for LadderCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.state = [zero(); 4];
+    }
+}
+
+impl<T> Control for Ladder<T>
+where
+    T: Frame + 'static,
+    T::Sample: Real,
+{
+    type Ctl<'a> = LadderCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
+}
+
+#[must_use]
+pub fn ladder<T>(
+    cutoff: Fp,
+    resonance: Fp,
+) -> Ladder<T>
+where
+    T: Frame,
+    T::Sample: Real,
+{
+    Ladder::new(cutoff, resonance)
+}
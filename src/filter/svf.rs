@@ -0,0 +1,352 @@
+use super::Response;
+use crate::{
+    context::Context,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
+    frame::Frame,
+    node::Node,
+    num::{
+        zero,
+        Complex,
+        Float,
+        Fp,
+        Real,
+    },
+};
+
+/// Output of [`Svf`] used when processed as a node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SvfMode {
+    #[default]
+    Lowpass,
+    Hipass,
+    Bandpass,
+    Notch,
+    Peak,
+}
+
+/// All outputs of [`Svf`] for one frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SvfOut<T> {
+    pub lowpass:  T,
+    pub hipass:   T,
+    pub bandpass: T,
+    pub notch:    T,
+    pub peak:     T,
+}
+
+// Cutoff and resonance with derived coefficients.
+#[derive(Debug, Clone, Copy)]
+struct SvfCoefs {
+    cutoff: Fp,
+    q:      Fp,
+    g:      Fp,
+    k:      Fp,
+    a1:     Fp,
+    a2:     Fp,
+    a3:     Fp,
+}
+
+impl SvfCoefs {
+    fn new(
+        cutoff: Fp,
+        q: Fp,
+    ) -> Self {
+        let g = <Fp as Real>::tan(<Fp as Real>::PI * cutoff);
+        let k = 1. / q;
+        let a1 = 1. / (1. + g * (g + k));
+        let a2 = g * a1;
+        let a3 = g * a2;
+        Self {
+            cutoff,
+            q,
+            g,
+            k,
+            a1,
+            a2,
+            a3,
+        }
+    }
+}
+
+/// State variable filter discretized with the topology-preserving
+/// transform (zero-delay feedback).
+///
+/// Computes lowpass, hipass, bandpass, notch and peak outputs at once.
+/// Unlike [`Biquad`](super::Biquad), the state is kept in integrators, so
+/// the filter stays stable when the cutoff is modulated, even at audio
+/// rate.
+///
+/// Design by Andrew Simper, after Vadim Zavalishin's _The Art of VA Filter
+/// Design_.
+#[derive(Debug, Clone)]
+pub struct Svf<T> {
+    pub mode: SvfMode,
+    coefs:    SvfCoefs,
+    ic1eq:    T,
+    ic2eq:    T,
+}
+
+impl<T> Svf<T>
+where
+    T: Frame,
+{
+    /// Filter with `cutoff` as a fraction of the sample rate and resonance
+    /// `q`.  `q = 1/sqrt(2)` gives the Butterworth response.
+    #[must_use]
+    pub fn new(
+        mode: SvfMode,
+        cutoff: Fp,
+        q: Fp,
+    ) -> Self {
+        Self {
+            mode,
+            coefs: SvfCoefs::new(cutoff, q),
+            ic1eq: zero(),
+            ic2eq: zero(),
+        }
+    }
+
+    /// Filter with cutoff in Hz.
+    #[must_use]
+    pub fn hz(
+        mode: SvfMode,
+        cutoff: Fp,
+        q: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(mode, ctx.hz(cutoff), q)
+    }
+
+    #[must_use]
+    pub fn cutoff(&self) -> Fp {
+        self.coefs.cutoff
+    }
+
+    #[must_use]
+    pub fn q(&self) -> Fp {
+        self.coefs.q
+    }
+
+    /// Set cutoff and resonance, keeping the state.
+    pub fn set(
+        &mut self,
+        cutoff: Fp,
+        q: Fp,
+    ) {
+        self.coefs = SvfCoefs::new(cutoff, q);
+    }
+
+    pub fn set_cutoff(
+        &mut self,
+        cutoff: Fp,
+    ) {
+        self.set(cutoff, self.coefs.q);
+    }
+
+    pub fn set_q(
+        &mut self,
+        q: Fp,
+    ) {
+        self.set(self.coefs.cutoff, q);
+    }
+
+    /// Process one frame and return all outputs.
+    pub fn tick_all(
+        &mut self,
+        frame: T,
+    ) -> SvfOut<T> {
+        let SvfCoefs {
+            k,
+            a1,
+            a2,
+            a3,
+            ..
+        } = self.coefs;
+        let v3 = frame - self.ic2eq;
+        let v1 = self.ic1eq * a1.to_float() + v3 * a2.to_float();
+        let v2 = self.ic2eq + self.ic1eq * a2.to_float() + v3 * a3.to_float();
+        self.ic1eq = v1 + v1 - self.ic1eq;
+        self.ic2eq = v2 + v2 - self.ic2eq;
+
+        let lowpass = v2;
+        let bandpass = v1;
+        let hipass = frame - v1 * k.to_float() - v2;
+        SvfOut {
+            lowpass,
+            hipass,
+            bandpass,
+            notch: lowpass + hipass,
+            peak: lowpass - hipass,
+        }
+    }
+
+    /// Process with the cutoff changing at each frame.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `cutoff` is shorter than `frames`.
+    pub fn proc_cutoff(
+        &mut self,
+        frames: &mut [T],
+        cutoff: &[Fp],
+    ) {
+        assert!(cutoff.len() >= frames.len(), "cutoff too short");
+        for (frm, c) in frames.iter_mut().zip(cutoff) {
+            self.set_cutoff(*c);
+            let out = self.tick_all(*frm);
+            *frm = self.select(out);
+        }
+    }
+
+    fn select(
+        &self,
+        out: SvfOut<T>,
+    ) -> T {
+        match self.mode {
+            SvfMode::Lowpass => out.lowpass,
+            SvfMode::Hipass => out.hipass,
+            SvfMode::Bandpass => out.bandpass,
+            SvfMode::Notch => out.notch,
+            SvfMode::Peak => out.peak,
+        }
+    }
+
+    fn ctl(&mut self) -> SvfCtl<'_, T> {
+        SvfCtl {
+            mode:  &mut self.mode,
+            coefs: &mut self.coefs,
+            ic1eq: &mut self.ic1eq,
+            ic2eq: &mut self.ic2eq,
+        }
+    }
+}
+
+impl<T> Node for Svf<T>
+where
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        for frm in frames {
+            let out = self.tick_all(*frm);
+            *frm = self.select(out);
+        }
+    }
+
+    fn reset_state(&mut self) {
+        self.ctl().reset();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        let (mut cutoff, mut q) = (self.coefs.cutoff, self.coefs.q);
+        visitor.param("cutoff", &mut cutoff);
+        visitor.param("q", &mut q);
+        if cutoff != self.coefs.cutoff || q != self.coefs.q {
+            self.set(cutoff, q);
+        }
+    }
+}
+
+/// Response of the selected output.
+impl<T> Response for Svf<T> {
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        // analog prototype with the cutoff prewarped to 1
+        let z1 = Complex::cis(-<Fp as Real>::TAU * freq);
+        let one = Complex::from(1.);
+        let s = (one - z1) / (one + z1) * self.coefs.g.recip();
+        let s2 = s * s;
+        let den = s2 + s * self.coefs.k + one;
+        let num = match self.mode {
+            SvfMode::Lowpass => one,
+            SvfMode::Hipass => s2,
+            SvfMode::Bandpass => s,
+            SvfMode::Notch => s2 + one,
+            SvfMode::Peak => one - s2,
+        };
+        num / den
+    }
+}
+
+/// Handle to parameters and state of [`Svf`].
+///
+/// Reset clears the state of the integrators.
+#[derive(Debug)]
+pub struct SvfCtl<'a, T> {
+    pub mode: &'a mut SvfMode,
+    coefs:    &'a mut SvfCoefs,
+    ic1eq:    &'a mut T,
+    ic2eq:    &'a mut T,
+}
+
+impl<'a, T> SvfCtl<'a, T> {
+    #[must_use]
+    pub fn cutoff(&self) -> Fp {
+        self.coefs.cutoff
+    }
+
+    #[must_use]
+    pub fn q(&self) -> Fp {
+        self.coefs.q
+    }
+
+    pub fn set_cutoff(
+        &mut self,
+        cutoff: Fp,
+    ) {
+        *self.coefs = SvfCoefs::new(cutoff, self.coefs.q);
+    }
+
+    pub fn set_q(
+        &mut self,
+        q: Fp,
+    ) {
+        *self.coefs = SvfCoefs::new(self.coefs.cutoff, q);
+    }
+}
+
+impl<'a, T> Reset for SvfCtl<'a, T>
+where
+    T: Frame,
+{
+    fn reset(&mut self) {
+        *self.ic1eq = zero();
+        *self.ic2eq = zero();
+    }
+}
+
+impl<T> Control for Svf<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = SvfCtl<'a, T> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut self.ctl());
+    }
+}
+
+#[must_use]
+pub fn svf<T: Frame>(
+    mode: SvfMode,
+    cutoff: Fp,
+    q: Fp,
+) -> Svf<T> {
+    Svf::new(mode, cutoff, q)
+}
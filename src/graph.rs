@@ -0,0 +1,635 @@
+//! Directed acyclic graph of nodes.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    error::Error,
+    fmt::{
+        Debug,
+        Display,
+    },
+    mem,
+};
+
+use crate::{
+    control::Visit,
+    feedback::Del,
+    frame::Frame,
+    node::Node,
+    num::{
+        one,
+        zero,
+        Float,
+        Fp,
+    },
+    Buf,
+};
+
+/// Endpoint of a connection in a [`Graph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vertex {
+    /// Signal entering the graph.
+    Input,
+    /// Signal leaving the graph.
+    Output,
+    /// Node with the given index.
+    Node(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GraphError {
+    /// Connection would close a cycle.
+    Cycle,
+    /// Vertex does not exist or cannot be used at this end of a
+    /// connection.
+    InvalidVertex(Vertex),
+}
+
+impl Display for GraphError {
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        match self {
+            GraphError::Cycle => write!(f, "connection would close a cycle"),
+            GraphError::InvalidVertex(v) => write!(f, "invalid vertex: {v:?}"),
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+struct Edge<T, A>
+where
+    A: Allocator,
+{
+    from:  Vertex,
+    to:    Vertex,
+    gain:  Fp,
+    delay: Option<Del<T, A>>,
+}
+
+impl<T, A> Edge<T, A>
+where
+    A: Allocator,
+{
+    fn delay_len(&self) -> usize {
+        self.delay.as_ref().map_or(0, |d| d.as_slice().len())
+    }
+}
+
+impl<T, A> Debug for Edge<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Edge")
+            .field("from", &self.from)
+            .field("to", &self.to)
+            .field("gain", &self.gain)
+            .field("delay", &self.delay_len())
+            .finish()
+    }
+}
+
+/// Audio graph with arbitrary node-to-node routing.
+///
+/// Nodes are vertices and connections are edges.  The input of each node is
+/// the sum of all its incoming connections, scaled by the gain of each
+/// connection.  The output of a node can be sent to any number of nodes.
+/// The output of the graph is the sum of all connections to
+/// [`Vertex::Output`].
+///
+/// Every node owns a buffer of `size` frames, allocated when the node is
+/// added.  Nodes are kept in topological order and processing never
+/// allocates.  Editing the graph (adding nodes, connecting) may allocate.
+///
+/// With latency compensation enabled, connections are delayed so that all
+/// signals arriving at a node, or at the output, are time-aligned.
+pub struct Graph<'a, T, A = Global>
+where
+    A: Allocator,
+{
+    nodes:        Vec<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>, A>,
+    bufs:         Vec<Buf<T, A>, A>,
+    edges:        Vec<Edge<T, A>, A>,
+    order:        Vec<usize, A>,
+    // latency at the output of each node, as of the last edit
+    latencies:    Vec<usize, A>,
+    // see `Mix::compensation`
+    compensation: Option<fn(&mut Self)>,
+    input:        Buf<T, A>,
+    scratch:      Buf<T, A>,
+    alloc:        A,
+}
+
+impl<'a, T, A> Graph<'a, T, A>
+where
+    A: Allocator + Clone,
+{
+    /// # Panics
+    ///
+    /// Panics if `size == 0`.
+    pub fn new_in(
+        size: usize,
+        alloc: A,
+    ) -> Self
+    where
+        T: Default,
+    {
+        assert!(size > 0, "block size must be positive");
+        Self {
+            nodes: Vec::new_in(alloc.clone()),
+            bufs: Vec::new_in(alloc.clone()),
+            edges: Vec::new_in(alloc.clone()),
+            order: Vec::new_in(alloc.clone()),
+            latencies: Vec::new_in(alloc.clone()),
+            compensation: None,
+            input: Buf::alloc_new_in(size, alloc.clone()),
+            scratch: Buf::alloc_new_in(size, alloc.clone()),
+            alloc,
+        }
+    }
+
+    /// Maximal number of frames processed at once.
+    #[must_use]
+    pub fn block_size(&self) -> usize {
+        self.input.len()
+    }
+
+    /// Number of nodes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Add an unconnected node to the graph.
+    pub fn add(
+        &mut self,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Vertex
+    where
+        T: Default,
+    {
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        self.bufs
+            .push(Buf::alloc_new_in(self.block_size(), self.alloc.clone()));
+        self.order.push(index);
+        // no latency until connected
+        self.latencies.push(0);
+        Vertex::Node(index)
+    }
+
+    pub fn node_add_in<N>(
+        &mut self,
+        node: N,
+        alloc: A,
+    ) -> Vertex
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.add(Box::new_in(node, alloc))
+    }
+
+    /// Connect the output of `from` to the input of `to` with unit gain.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection would close a cycle, or if either
+    /// vertex is invalid.  The graph is left unchanged then.
+    pub fn connect(
+        &mut self,
+        from: Vertex,
+        to: Vertex,
+    ) -> Result<(), GraphError>
+    where
+        T: Frame,
+    {
+        self.connect_gain(from, to, one())
+    }
+
+    /// Connect the output of `from` to the input of `to`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the connection would close a cycle, or if either
+    /// vertex is invalid.  The graph is left unchanged then.
+    pub fn connect_gain(
+        &mut self,
+        from: Vertex,
+        to: Vertex,
+        gain: Fp,
+    ) -> Result<(), GraphError>
+    where
+        T: Frame,
+    {
+        match from {
+            Vertex::Output => return Err(GraphError::InvalidVertex(from)),
+            Vertex::Node(i) if i >= self.len() => {
+                return Err(GraphError::InvalidVertex(from))
+            }
+            _ => (),
+        }
+        match to {
+            Vertex::Input => return Err(GraphError::InvalidVertex(to)),
+            Vertex::Node(i) if i >= self.len() => {
+                return Err(GraphError::InvalidVertex(to))
+            }
+            _ => (),
+        }
+        if from == to {
+            return Err(GraphError::Cycle);
+        }
+
+        self.edges.push(Edge {
+            from,
+            to,
+            gain,
+            delay: None,
+        });
+        if self.sort().is_err() {
+            self.edges.pop();
+            self.sort().expect("graph was acyclic before");
+            return Err(GraphError::Cycle);
+        }
+        self.update_latencies();
+        Ok(())
+    }
+
+    /// Remove all connections from `from` to `to`.
+    ///
+    /// Returns `true` if any connection was removed.
+    pub fn disconnect(
+        &mut self,
+        from: Vertex,
+        to: Vertex,
+    ) -> bool
+    where
+        T: Frame,
+    {
+        let len = self.edges.len();
+        self.edges.retain(|e| e.from != from || e.to != to);
+        let removed = self.edges.len() != len;
+        if removed {
+            self.sort().expect("removing edges keeps graph acyclic");
+            self.update_latencies();
+        }
+        removed
+    }
+
+    /// Gain of the connection from `from` to `to`, if connected.
+    pub fn gain_mut(
+        &mut self,
+        from: Vertex,
+        to: Vertex,
+    ) -> Option<&mut Fp> {
+        self.edges
+            .iter_mut()
+            .find(|e| e.from == from && e.to == to)
+            .map(|e| &mut e.gain)
+    }
+
+    /// Replace the node, keeping its connections.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    pub fn replace(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Box<dyn Node<Frame = T> + Sync + Send + 'a, A>
+    where
+        T: Frame,
+    {
+        let node = mem::replace(&mut self.nodes[index], node);
+        self.update_latencies();
+        node
+    }
+
+    #[must_use]
+    pub fn compensation(&self) -> bool {
+        self.compensation.is_some()
+    }
+
+    /// Enable or disable latency compensation, as
+    /// [`Mix::set_compensation`](crate::mix::Mix::set_compensation) does.
+    /// Call [`compensate`](Self::compensate) after editing the graph.  Both
+    /// allocate, so call them off the audio thread.
+    pub fn set_compensation(
+        &mut self,
+        compensation: bool,
+    ) where
+        T: Frame,
+    {
+        self.compensation = compensation.then_some(Self::compensate);
+        if compensation {
+            self.compensate();
+        } else {
+            for e in &mut self.edges {
+                e.delay = None;
+            }
+        }
+    }
+
+    /// Time-align signals by delaying connections from paths with shorter
+    /// latency.  Delay lines are handled as in
+    /// [`Mix::compensate`](crate::mix::Mix::compensate).
+    pub fn compensate(&mut self)
+    where
+        T: Frame,
+    {
+        self.update_latencies();
+        let lat = &self.latencies;
+        let source = |v| match v {
+            Vertex::Node(u) => lat[u],
+            _ => 0,
+        };
+        // latency at the input of each node; the last entry is the output
+        let mut arrival = Vec::new_in(self.alloc.clone());
+        arrival.resize(self.nodes.len() + 1, 0);
+        for e in &self.edges {
+            let i = match e.to {
+                Vertex::Node(v) => v,
+                _ => self.nodes.len(),
+            };
+            arrival[i] = arrival[i].max(source(e.from));
+        }
+        for e in &mut self.edges {
+            let i = match e.to {
+                Vertex::Node(v) => v,
+                _ => self.nodes.len(),
+            };
+            let len = arrival[i] - source(e.from);
+            if e.delay_len() != len {
+                e.delay = (len > 0)
+                    .then(|| Del::alloc_new_in(len, self.alloc.clone()));
+            }
+        }
+    }
+
+    pub fn as_slice(
+        &self
+    ) -> &[Box<dyn Node<Frame = T> + Sync + Send + 'a, A>] {
+        &self.nodes
+    }
+
+    pub fn as_mut_slice(
+        &mut self
+    ) -> &mut [Box<dyn Node<Frame = T> + Sync + Send + 'a, A>] {
+        &mut self.nodes
+    }
+
+    // Kahn's algorithm.  Leaves `order` incomplete if there is a cycle.
+    fn sort(&mut self) -> Result<(), GraphError> {
+        let mut indeg = Vec::new_in(self.alloc.clone());
+        indeg.resize(self.nodes.len(), 0_usize);
+        for e in &self.edges {
+            if let (Vertex::Node(_), Vertex::Node(v)) = (e.from, e.to) {
+                indeg[v] += 1;
+            }
+        }
+
+        self.order.clear();
+        self.order
+            .extend((0..self.nodes.len()).filter(|&v| indeg[v] == 0));
+        let mut i = 0;
+        while i < self.order.len() {
+            let u = Vertex::Node(self.order[i]);
+            i += 1;
+            for e in &self.edges {
+                if let (true, Vertex::Node(v)) = (e.from == u, e.to) {
+                    indeg[v] -= 1;
+                    if indeg[v] == 0 {
+                        self.order.push(v);
+                    }
+                }
+            }
+        }
+
+        if self.order.len() == self.nodes.len() {
+            Ok(())
+        } else {
+            Err(GraphError::Cycle)
+        }
+    }
+}
+
+// Accumulate connections going into `to`.  If `to` is a node, its own buffer
+// is split out: `before` holds buffers of nodes with smaller index and `after`
+// of nodes with greater index.  Delayed connections are processed in
+// `scratch`.
+fn gather<T, A>(
+    out: &mut [T],
+    to: Vertex,
+    edges: &mut [Edge<T, A>],
+    input: &[T],
+    before: &[Buf<T, A>],
+    after: &[Buf<T, A>],
+    scratch: &mut [T],
+) where
+    T: Frame,
+    A: Allocator,
+{
+    let len = out.len();
+    out.fill(zero());
+    for e in edges.iter_mut().filter(|e| e.to == to) {
+        let src = match e.from {
+            Vertex::Input => input,
+            Vertex::Node(u) if u < before.len() => before[u].as_slice(),
+            Vertex::Node(u) => after[u - before.len() - 1].as_slice(),
+            Vertex::Output => unreachable!(),
+        };
+        let src = match &mut e.delay {
+            Some(delay) => {
+                let scratch = &mut scratch[..len];
+                scratch.copy_from_slice(&src[..len]);
+                delay.proc(scratch);
+                &*scratch
+            }
+            None => src,
+        };
+        let gain = e.gain.to_float();
+        for (y, x) in out.iter_mut().zip(src) {
+            *y += *x * gain;
+        }
+    }
+}
+
+impl<'a, T, A> Graph<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    // Latency at the output of each node, assuming all signals arriving at a
+    // node are aligned to the latest one.  Never allocates once all nodes
+    // are added.
+    fn update_latencies(&mut self) {
+        for &v in self.order.iter() {
+            let arrival = self
+                .edges
+                .iter()
+                .filter(|e| e.to == Vertex::Node(v))
+                .filter_map(|e| match e.from {
+                    Vertex::Node(u) => Some(self.latencies[u]),
+                    Vertex::Input => Some(0),
+                    Vertex::Output => None,
+                })
+                .max()
+                .unwrap_or(0);
+            self.latencies[v] = arrival + self.nodes[v].latency();
+        }
+    }
+}
+
+impl<'a, T, A> Node for Graph<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let size = self.input.len();
+        for chunk in frames.chunks_mut(size) {
+            let len = chunk.len();
+            let input = &mut self.input.as_mut_slice()[..len];
+            input.copy_from_slice(chunk);
+
+            for &v in self.order.iter() {
+                let (before, rest) = self.bufs.split_at_mut(v);
+                let (buf, after) =
+                    rest.split_first_mut().expect("buffer for each node");
+                let buf = &mut buf.as_mut_slice()[..len];
+                gather(
+                    buf,
+                    Vertex::Node(v),
+                    &mut self.edges,
+                    input,
+                    before,
+                    after,
+                    self.scratch.as_mut_slice(),
+                );
+                self.nodes[v].proc(buf);
+            }
+
+            gather(
+                chunk,
+                Vertex::Output,
+                &mut self.edges,
+                input,
+                &self.bufs,
+                &[],
+                self.scratch.as_mut_slice(),
+            );
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        for node in &mut self.nodes {
+            node.prepare(max_block, sample_rate);
+        }
+        self.update_latencies();
+        if let Some(compensate) = self.compensation {
+            compensate(self);
+        }
+    }
+
+    /// Latency of the longest path from the input to the output.
+    ///
+    /// Latencies of nodes are taken when the graph was last edited,
+    /// prepared or compensated.
+    fn latency(&self) -> usize {
+        let mut output = 0;
+        for e in self.edges.iter().filter(|e| e.to == Vertex::Output) {
+            if let Vertex::Node(u) = e.from {
+                output = output.max(self.latencies[u]);
+            }
+        }
+        output
+    }
+
+    fn reset_state(&mut self) {
+        for node in &mut self.nodes {
+            node.reset_state();
+        }
+        for delay in self.edges.iter_mut().filter_map(|e| e.delay.as_mut()) {
+            delay.reset_state();
+        }
+    }
+
+    /// Visit parameters of each node.  Gains of connections are not
+    /// visited.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        for (i, node) in self.nodes.iter_mut().enumerate() {
+            visitor.enter(i);
+            node.visit_params(visitor);
+            visitor.leave();
+        }
+    }
+}
+
+impl<'a, T, A> Debug for Graph<'a, T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Graph")
+            .field("nodes", &format_args!("Vec<Box<dyn Node>>"))
+            .field("edges", &self.edges)
+            .field("order", &self.order)
+            .field("compensation", &self.compensation.is_some())
+            .field("block_size", &self.input.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Graph<'a, T> {
+    /// # Panics
+    ///
+    /// Panics if `size == 0`.
+    #[must_use]
+    pub fn new(size: usize) -> Self
+    where
+        T: Default,
+    {
+        Self::new_in(size, Global)
+    }
+
+    /// Allocates memory on the heap
+    pub fn node_add<N>(
+        &mut self,
+        node: N,
+    ) -> Vertex
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.add(Box::new(node))
+    }
+}
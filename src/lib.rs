@@ -31,20 +31,34 @@
 mod buffer;
 pub use buffer::Buf;
 
+mod context;
+pub use context::Context;
+
 mod control;
 pub use control::{
     Control,
     Reset,
+    Visit,
 };
 
+pub mod analyzer;
 pub mod bus;
+pub mod convolve;
 pub mod envelope;
 pub mod feedback;
+pub mod fft;
 pub mod filter;
 pub mod frame;
+pub mod graph;
 pub mod math;
+pub mod mix;
 pub mod node;
 pub mod noise;
 pub mod num;
 pub mod oscillator;
+pub mod processor;
+pub mod sched;
+pub mod smooth;
+pub mod sync;
 pub mod util;
+pub mod window;
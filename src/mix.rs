@@ -0,0 +1,435 @@
+//! Parallel mixing bus.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    fmt::Debug,
+    mem,
+    ops::{
+        Index,
+        IndexMut,
+    },
+};
+
+use crate::{
+    control::Visit,
+    feedback::Del,
+    frame::Frame,
+    node::Node,
+    num::{
+        one,
+        zero,
+        Float,
+        Fp,
+    },
+    Buf,
+};
+
+/// Parallel counterpart of [`Bus`](crate::bus::Bus).
+///
+/// Every node receives the same input.  Their outputs are scaled by per-branch
+/// gain and summed.  Processing is done in chunks of at most `size` frames
+/// using scratch buffers allocated up front, so no memory is allocated
+/// while processing.  An empty mix outputs silence.
+///
+/// With latency compensation enabled, branches with latency shorter than the
+/// maximal one are delayed, so that all branches are time-aligned.
+pub struct Mix<'a, T, A = Global>
+where
+    A: Allocator,
+{
+    nodes:        Vec<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>, A>,
+    gains:        Vec<Fp, A>,
+    delays:       Vec<Option<Del<T, A>>, A>,
+    // `compensate`, kept while compensation is enabled so that `prepare`
+    // can call it without the allocator being `Clone`
+    compensation: Option<fn(&mut Self)>,
+    input:        Buf<T, A>,
+    scratch:      Buf<T, A>,
+    alloc:        A,
+}
+
+impl<'a, T, A> Mix<'a, T, A>
+where
+    A: Allocator,
+{
+    /// # Panics
+    ///
+    /// Panics if `size == 0`.
+    pub fn new_in(
+        size: usize,
+        alloc: A,
+    ) -> Self
+    where
+        A: Clone,
+        T: Default,
+    {
+        assert!(size > 0, "block size must be positive");
+        Self {
+            nodes: Vec::new_in(alloc.clone()),
+            gains: Vec::new_in(alloc.clone()),
+            delays: Vec::new_in(alloc.clone()),
+            compensation: None,
+            input: Buf::alloc_new_in(size, alloc.clone()),
+            scratch: Buf::alloc_new_in(size, alloc.clone()),
+            alloc,
+        }
+    }
+
+    /// Maximal number of frames processed at once.
+    #[must_use]
+    pub fn block_size(&self) -> usize {
+        self.input.len()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Add a branch with unit gain.
+    pub fn push(
+        &mut self,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) {
+        self.nodes.push(node);
+        self.gains.push(one());
+        self.delays.push(None);
+    }
+
+    pub fn pop(
+        &mut self
+    ) -> Option<Box<dyn Node<Frame = T> + Sync + Send + 'a, A>> {
+        self.gains.pop();
+        self.delays.pop();
+        self.nodes.pop()
+    }
+
+    /// Insert a branch with unit gain.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`
+    pub fn insert(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) {
+        self.nodes.insert(index, node);
+        self.gains.insert(index, one());
+        self.delays.insert(index, None);
+    }
+
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    pub fn remove(
+        &mut self,
+        index: usize,
+    ) -> Box<dyn Node<Frame = T> + Sync + Send + 'a, A> {
+        self.gains.remove(index);
+        self.delays.remove(index);
+        self.nodes.remove(index)
+    }
+
+    /// Replace the node, keeping the gain of the branch.
+    ///
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    pub fn replace(
+        &mut self,
+        index: usize,
+        node: Box<dyn Node<Frame = T> + Sync + Send + 'a, A>,
+    ) -> Box<dyn Node<Frame = T> + Sync + Send + 'a, A> {
+        mem::replace(&mut self.nodes[index], node)
+    }
+
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    #[must_use]
+    pub fn gain(
+        &self,
+        index: usize,
+    ) -> &Fp {
+        &self.gains[index]
+    }
+
+    /// # Panics
+    ///
+    /// Panics if index is out of bounds.
+    pub fn gain_mut(
+        &mut self,
+        index: usize,
+    ) -> &mut Fp {
+        &mut self.gains[index]
+    }
+
+    pub fn node_push_in<N>(
+        &mut self,
+        node: N,
+        alloc: A,
+    ) -> &mut Self
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.push(Box::new_in(node, alloc));
+        self
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index > len`
+    pub fn node_insert_in<N>(
+        &mut self,
+        index: usize,
+        node: N,
+        alloc: A,
+    ) -> &mut Self
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.insert(index, Box::new_in(node, alloc));
+        self
+    }
+
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.gains.clear();
+        self.delays.clear();
+    }
+
+    #[must_use]
+    pub fn compensation(&self) -> bool {
+        self.compensation.is_some()
+    }
+
+    /// Enable or disable latency compensation.
+    ///
+    /// If enabled, delays are allocated now and recomputed by
+    /// [`prepare`](Node::prepare).  Call [`compensate`](Self::compensate)
+    /// after changing branches.  This allocates, so call it off the audio
+    /// thread.
+    pub fn set_compensation(
+        &mut self,
+        compensation: bool,
+    ) where
+        A: Clone,
+        T: Frame,
+    {
+        self.compensation = compensation.then_some(Self::compensate);
+        if compensation {
+            self.compensate();
+        } else {
+            self.delays.fill_with(|| None);
+        }
+    }
+
+    /// Time-align branches by delaying the ones with shorter latency.
+    ///
+    /// Delay lines are allocated with the allocator of the mix, so call it
+    /// off the audio thread.  Delays of unchanged length are kept.
+    pub fn compensate(&mut self)
+    where
+        A: Clone,
+        T: Frame,
+    {
+        let max = self.nodes.iter().map(|n| n.latency()).max().unwrap_or(0);
+        for (node, delay) in self.nodes.iter().zip(self.delays.iter_mut()) {
+            let len = max - node.latency();
+            if delay.as_ref().map_or(0, |d| d.as_slice().len()) != len {
+                *delay = (len > 0)
+                    .then(|| Del::alloc_new_in(len, self.alloc.clone()));
+            }
+        }
+    }
+
+    pub fn as_slice(
+        &self
+    ) -> &[Box<dyn Node<Frame = T> + Sync + Send + 'a, A>] {
+        &self.nodes
+    }
+
+    pub fn as_mut_slice(
+        &mut self
+    ) -> &mut [Box<dyn Node<Frame = T> + Sync + Send + 'a, A>] {
+        &mut self.nodes
+    }
+}
+
+impl<'a, T, A> Index<usize> for Mix<'a, T, A>
+where
+    A: Allocator,
+{
+    type Output = Box<dyn Node<Frame = T> + Sync + Send + 'a, A>;
+
+    fn index(
+        &self,
+        index: usize,
+    ) -> &Self::Output {
+        &self.nodes[index]
+    }
+}
+
+impl<'a, T, A> IndexMut<usize> for Mix<'a, T, A>
+where
+    A: Allocator,
+{
+    fn index_mut(
+        &mut self,
+        index: usize,
+    ) -> &mut Self::Output {
+        &mut self.nodes[index]
+    }
+}
+
+impl<'a, T, A> Debug for Mix<'a, T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Mix")
+            .field("nodes", &format_args!("Vec<Box<dyn Node>>"))
+            .field("gains", &self.gains)
+            .field("compensation", &self.compensation())
+            .field("block_size", &self.block_size())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, A> Node for Mix<'a, T, A>
+where
+    A: Allocator,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let size = self.input.len();
+        for chunk in frames.chunks_mut(size) {
+            let input = &mut self.input.as_mut_slice()[..chunk.len()];
+            input.copy_from_slice(chunk);
+            chunk.fill(zero());
+
+            let branches = self
+                .nodes
+                .iter_mut()
+                .zip(&self.gains)
+                .zip(self.delays.iter_mut());
+            for ((node, gain), delay) in branches {
+                let scratch = &mut self.scratch.as_mut_slice()[..chunk.len()];
+                scratch.copy_from_slice(input);
+                node.proc(scratch);
+                if let Some(delay) = delay {
+                    delay.proc(scratch);
+                }
+
+                let gain = gain.to_float();
+                for (frm, x) in chunk.iter_mut().zip(scratch.iter()) {
+                    *frm += *x * gain;
+                }
+            }
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        for node in &mut self.nodes {
+            node.prepare(max_block, sample_rate);
+        }
+        if let Some(compensate) = self.compensation {
+            compensate(self);
+        }
+    }
+
+    /// Maximal latency of all branches.
+    fn latency(&self) -> usize {
+        self.nodes.iter().map(|n| n.latency()).max().unwrap_or(0)
+    }
+
+    fn reset_state(&mut self) {
+        for node in &mut self.nodes {
+            node.reset_state();
+        }
+        for delay in self.delays.iter_mut().flatten() {
+            delay.reset_state();
+        }
+    }
+
+    /// Visit the gain of each branch, then the parameters of its node.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        let branches = self.nodes.iter_mut().zip(self.gains.iter_mut());
+        for (i, (node, gain)) in branches.enumerate() {
+            visitor.enter(i);
+            visitor.param("gain", gain);
+            node.visit_params(visitor);
+            visitor.leave();
+        }
+    }
+}
+
+impl<'a, T> Mix<'a, T> {
+    /// # Panics
+    ///
+    /// Panics if `size == 0`.
+    #[must_use]
+    pub fn new(size: usize) -> Self
+    where
+        T: Default,
+    {
+        Self::new_in(size, Global)
+    }
+
+    /// Allocates memory on the heap
+    pub fn node_push<N>(
+        &mut self,
+        node: N,
+    ) -> &mut Self
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.push(Box::new(node));
+        self
+    }
+
+    /// Allocates memory on the heap
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index > len`
+    pub fn node_insert<N>(
+        &mut self,
+        index: usize,
+        node: N,
+    ) -> &mut Self
+    where
+        T: Frame,
+        N: Node<Frame = T> + Sync + Send + 'a,
+    {
+        self.insert(index, Box::new(node));
+        self
+    }
+}
@@ -10,7 +10,24 @@ use std::{
     marker::PhantomData,
 };
 
-use crate::frame::Frame;
+use crate::{
+    control::Visit,
+    frame::Frame,
+    num::Fp,
+};
+
+mod comb;
+
+pub use comb::{
+    Bypass,
+    Split,
+    Sum,
+    Then,
+};
+
+// Number of frames copied to the stack at once by nodes that need
+// a scratch buffer but do not allocate.
+pub(crate) const CHUNK: usize = 64;
 
 pub trait Node {
     type Frame: Frame;
@@ -28,6 +45,82 @@ pub trait Node {
         self.proc(frames);
         frames[0]
     }
+
+    /// Prepare for processing.
+    ///
+    /// Called before processing starts, and whenever the maximal number of
+    /// frames passed to [`proc`](Node::proc) at once, or the sample rate,
+    /// changes.  Nodes can preallocate here.  Does nothing by default.
+    fn prepare(
+        &mut self,
+        _max_block: usize,
+        _sample_rate: Fp,
+    ) {
+    }
+
+    /// Number of frames by which the output is delayed.
+    ///
+    /// Zero by default.
+    fn latency(&self) -> usize {
+        0
+    }
+
+    /// Clear internal state, e.g. filter history or delay buffers, as if
+    /// the node was just created.  Parameters are kept.
+    ///
+    /// Does nothing by default.
+    fn reset_state(&mut self) {}
+
+    /// Pass parameters to `visitor`.
+    ///
+    /// Containers pass parameters of each child between calls to
+    /// [`Visit::enter`] and [`Visit::leave`].  Does nothing by default.
+    fn visit_params(
+        &mut self,
+        _visitor: &mut dyn Visit,
+    ) {
+    }
+
+    /// Process with `self`, then with `next`.
+    fn then<N>(
+        self,
+        next: N,
+    ) -> Then<Self, N>
+    where
+        Self: Sized,
+        N: Node<Frame = Self::Frame>,
+    {
+        Then::new(self, next)
+    }
+
+    /// Feed `self` and `other` with the same input and sum their outputs.
+    fn mix<N>(
+        self,
+        other: N,
+    ) -> Sum<Self, N>
+    where
+        Self: Sized,
+        N: Node<Frame = Self::Frame>,
+    {
+        Sum::new(self, other)
+    }
+
+    /// Mix the output of `self` with the unprocessed input.
+    fn split(self) -> Split<Self>
+    where
+        Self: Sized,
+    {
+        Split::new(self)
+    }
+
+    /// Make `self` switchable, so that it can pass the signal through
+    /// unchanged.
+    fn bypass(self) -> Bypass<Self>
+    where
+        Self: Sized,
+    {
+        Bypass::new(self)
+    }
 }
 
 #[derive(Debug)]
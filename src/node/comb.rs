@@ -0,0 +1,428 @@
+use super::{
+    Node,
+    CHUNK,
+};
+use crate::{
+    control::Visit,
+    filter::Response,
+    num::{
+        one,
+        zero,
+        Complex,
+        Float,
+        Fp,
+    },
+};
+
+/// Serial composition: process with `N`, then with `M`.
+///
+/// See [`Node::then`].
+#[derive(Debug, Clone)]
+pub struct Then<N, M> {
+    first:  N,
+    second: M,
+}
+
+impl<N, M> Then<N, M> {
+    pub fn new(
+        first: N,
+        second: M,
+    ) -> Self {
+        Self {
+            first,
+            second,
+        }
+    }
+
+    pub fn first(&self) -> &N {
+        &self.first
+    }
+
+    pub fn first_mut(&mut self) -> &mut N {
+        &mut self.first
+    }
+
+    pub fn second(&self) -> &M {
+        &self.second
+    }
+
+    pub fn second_mut(&mut self) -> &mut M {
+        &mut self.second
+    }
+
+    pub fn into_inner(self) -> (N, M) {
+        (self.first, self.second)
+    }
+}
+
+impl<N, M> Node for Then<N, M>
+where
+    N: Node,
+    M: Node<Frame = N::Frame>,
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        self.first.proc(frames);
+        self.second.proc(frames);
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.first.prepare(max_block, sample_rate);
+        self.second.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.first.latency() + self.second.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.first.reset_state();
+        self.second.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.enter(0);
+        self.first.visit_params(visitor);
+        visitor.leave();
+        visitor.enter(1);
+        self.second.visit_params(visitor);
+        visitor.leave();
+    }
+}
+
+impl<N, M> Response for Then<N, M>
+where
+    N: Response,
+    M: Response,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.first.response(freq) * self.second.response(freq)
+    }
+}
+
+/// Parallel composition: sum of outputs of `N` and `M` fed with the same
+/// input.
+///
+/// See [`Node::mix`].
+#[derive(Debug, Clone)]
+pub struct Sum<N, M> {
+    first:  N,
+    second: M,
+}
+
+impl<N, M> Sum<N, M> {
+    pub fn new(
+        first: N,
+        second: M,
+    ) -> Self {
+        Self {
+            first,
+            second,
+        }
+    }
+
+    pub fn first(&self) -> &N {
+        &self.first
+    }
+
+    pub fn first_mut(&mut self) -> &mut N {
+        &mut self.first
+    }
+
+    pub fn second(&self) -> &M {
+        &self.second
+    }
+
+    pub fn second_mut(&mut self) -> &mut M {
+        &mut self.second
+    }
+
+    pub fn into_inner(self) -> (N, M) {
+        (self.first, self.second)
+    }
+}
+
+impl<N, M> Node for Sum<N, M>
+where
+    N: Node,
+    M: Node<Frame = N::Frame>,
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let mut buf = [zero::<Self::Frame>(); CHUNK];
+        for chunk in frames.chunks_mut(CHUNK) {
+            let copy = &mut buf[..chunk.len()];
+            copy.copy_from_slice(chunk);
+            self.first.proc(chunk);
+            self.second.proc(copy);
+            for (frm, x) in chunk.iter_mut().zip(copy.iter()) {
+                *frm += *x;
+            }
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.first.prepare(max_block, sample_rate);
+        self.second.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.first.latency().max(self.second.latency())
+    }
+
+    fn reset_state(&mut self) {
+        self.first.reset_state();
+        self.second.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.enter(0);
+        self.first.visit_params(visitor);
+        visitor.leave();
+        visitor.enter(1);
+        self.second.visit_params(visitor);
+        visitor.leave();
+    }
+}
+
+impl<N, M> Response for Sum<N, M>
+where
+    N: Response,
+    M: Response,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.first.response(freq) + self.second.response(freq)
+    }
+}
+
+/// Split the signal into a dry path and a path processed by `N`, then mix
+/// them together.
+///
+/// See [`Node::split`].
+#[derive(Debug, Clone)]
+pub struct Split<N> {
+    node:    N,
+    pub dry: Fp,
+    pub wet: Fp,
+}
+
+impl<N> Split<N> {
+    /// Both paths with unit gain.
+    pub fn new(node: N) -> Self {
+        Self {
+            node,
+            dry: one(),
+            wet: one(),
+        }
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    pub fn into_node(self) -> N {
+        self.node
+    }
+}
+
+impl<N> Node for Split<N>
+where
+    N: Node,
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let dry = self.dry.to_float();
+        let wet = self.wet.to_float();
+        let mut buf = [zero::<Self::Frame>(); CHUNK];
+        for chunk in frames.chunks_mut(CHUNK) {
+            let copy = &mut buf[..chunk.len()];
+            copy.copy_from_slice(chunk);
+            self.node.proc(chunk);
+            for (frm, x) in chunk.iter_mut().zip(copy.iter()) {
+                *frm = *frm * wet + *x * dry;
+            }
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.node.prepare(max_block, sample_rate);
+    }
+
+    /// Latency of the wet path.  The dry path is not delayed.
+    fn latency(&self) -> usize {
+        self.node.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.node.reset_state();
+    }
+
+    /// Visit `dry` and `wet`, then the parameters of the node.
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visitor.param("dry", &mut self.dry);
+        visitor.param("wet", &mut self.wet);
+        self.node.visit_params(visitor);
+    }
+}
+
+impl<N> Response for Split<N>
+where
+    N: Response,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        self.node.response(freq) * self.wet + Complex::from(self.dry)
+    }
+}
+
+/// Node that can be switched off, passing the signal through unchanged.
+///
+/// See [`Node::bypass`].
+#[derive(Debug, Clone)]
+pub struct Bypass<N> {
+    node:     N,
+    bypassed: bool,
+}
+
+impl<N> Bypass<N> {
+    /// The node is active.
+    pub fn new(node: N) -> Self {
+        Self {
+            node,
+            bypassed: false,
+        }
+    }
+
+    #[must_use]
+    pub fn is_bypassed(&self) -> bool {
+        self.bypassed
+    }
+
+    pub fn set_bypassed(
+        &mut self,
+        bypassed: bool,
+    ) {
+        self.bypassed = bypassed;
+    }
+
+    pub fn toggle(&mut self) {
+        self.bypassed = !self.bypassed;
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    pub fn into_node(self) -> N {
+        self.node
+    }
+}
+
+impl<N> Node for Bypass<N>
+where
+    N: Node,
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        if !self.bypassed {
+            self.node.proc(frames);
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.node.prepare(max_block, sample_rate);
+    }
+
+    /// Zero, if bypassed.
+    fn latency(&self) -> usize {
+        if self.bypassed {
+            0
+        } else {
+            self.node.latency()
+        }
+    }
+
+    fn reset_state(&mut self) {
+        self.node.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.node.visit_params(visitor);
+    }
+}
+
+impl<N> Response for Bypass<N>
+where
+    N: Response,
+{
+    fn response(
+        &self,
+        freq: Fp,
+    ) -> Complex {
+        if self.bypassed {
+            Complex::from(1.)
+        } else {
+            self.node.response(freq)
+        }
+    }
+}
@@ -1,6 +1,7 @@
 use std::ops::{
     Add,
     AddAssign,
+    Div,
     Mul,
     MulAssign,
     Neg,
@@ -489,3 +490,194 @@ macro_rules! impl_real {
 }
 
 impl_real!(f32 f64);
+
+/// Complex number.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Complex {
+    pub re: Fp,
+    pub im: Fp,
+}
+
+impl Complex {
+    #[must_use]
+    pub const fn new(
+        re: Fp,
+        im: Fp,
+    ) -> Self {
+        Self {
+            re,
+            im,
+        }
+    }
+
+    /// Complex number with modulus `r` and argument `theta`.
+    #[must_use]
+    pub fn from_polar(
+        r: Fp,
+        theta: Fp,
+    ) -> Self {
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
+
+    /// `exp(i * theta)`
+    #[must_use]
+    pub fn cis(theta: Fp) -> Self {
+        Self::from_polar(1., theta)
+    }
+
+    #[must_use]
+    pub fn conj(self) -> Self {
+        Self::new(self.re, -self.im)
+    }
+
+    /// Squared modulus.
+    #[must_use]
+    pub fn norm_sqr(self) -> Fp {
+        self.re * self.re + self.im * self.im
+    }
+
+    /// Modulus.
+    #[must_use]
+    pub fn norm(self) -> Fp {
+        self.re.hypot(self.im)
+    }
+
+    /// Argument in `(-PI, PI]`.
+    #[must_use]
+    pub fn arg(self) -> Fp {
+        self.im.atan2(self.re)
+    }
+
+    #[must_use]
+    pub fn recip(self) -> Self {
+        let n = self.norm_sqr();
+        Self::new(self.re / n, -self.im / n)
+    }
+
+    /// Principal square root.
+    #[must_use]
+    pub fn sqrt(self) -> Self {
+        Self::from_polar(self.norm().sqrt(), self.arg() / 2.)
+    }
+}
+
+impl Zero for Complex {
+    fn zero() -> Self {
+        Self::new(0., 0.)
+    }
+}
+
+impl One for Complex {
+    fn one() -> Self {
+        Self::new(1., 0.)
+    }
+}
+
+impl From<Fp> for Complex {
+    fn from(value: Fp) -> Self {
+        Self::new(value, 0.)
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl AddAssign for Complex {
+    fn add_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl SubAssign for Complex {
+    fn sub_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = *self - rhs;
+    }
+}
+
+impl Neg for Complex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.re, -self.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+impl MulAssign for Complex {
+    fn mul_assign(
+        &mut self,
+        rhs: Self,
+    ) {
+        *self = *self * rhs;
+    }
+}
+
+impl Mul<Fp> for Complex {
+    type Output = Self;
+
+    fn mul(
+        self,
+        rhs: Fp,
+    ) -> Self::Output {
+        Self::new(self.re * rhs, self.im * rhs)
+    }
+}
+
+impl MulAssign<Fp> for Complex {
+    fn mul_assign(
+        &mut self,
+        rhs: Fp,
+    ) {
+        *self = *self * rhs;
+    }
+}
+
+impl Div for Complex {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(
+        self,
+        rhs: Self,
+    ) -> Self::Output {
+        self * rhs.recip()
+    }
+}
@@ -4,6 +4,12 @@ use std::{
 };
 
 use crate::{
+    context::Context,
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
     frame::Frame,
     math::lin,
     node::Node,
@@ -13,12 +19,36 @@ use crate::{
         Fp,
         Real,
     },
+    smooth::{
+        visit_param,
+        Glide,
+        Smooth,
+    },
 };
 
+/// Handle to phase and frequency of an oscillator.
+///
+/// Reset sets the phase to zero.  The glide of frequency goes on.
+#[derive(Debug)]
+pub struct OscCtl<'a> {
+    pub phase: &'a mut Fp,
+    pub freq:  &'a mut Glide,
+}
+
+impl<'a> Reset for OscCtl<'a> {
+    fn reset(&mut self) {
+        *self.phase = 0.;
+    }
+}
+
+/// Sine oscillator.
+///
+/// Changes of frequency glide over [`Glide::time`] frames, instantly by
+/// default.
 #[derive(Debug, Clone)]
 pub struct Sine<T> {
     pub phase: Fp,
-    pub freq:  Fp,
+    pub freq:  Glide,
     _marker:   PhantomData<T>,
 }
 
@@ -26,11 +56,20 @@ impl<T> Sine<T> {
     #[must_use]
     pub fn new(freq: Fp) -> Self {
         Self {
-            phase: 0.,
-            freq,
+            phase:   0.,
+            freq:    Glide::from(freq),
             _marker: PhantomData,
         }
     }
+
+    /// Sine with frequency in Hz.
+    #[must_use]
+    pub fn hz(
+        freq: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(ctx.hz(freq))
+    }
 }
 
 impl<T> From<Fp> for Sine<T> {
@@ -56,19 +95,48 @@ where
             *frm = Self::Frame::splat(
                 (T::Sample::from_float(self.phase) * tau()).sin(),
             );
-            self.phase += self.freq;
+            self.phase += self.freq.tick();
             while self.phase >= 1. {
                 self.phase -= 1.;
             }
         }
     }
+
+    /// Set the phase to zero.
+    fn reset_state(&mut self) {
+        self.phase = 0.;
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visit_param(visitor, "freq", &mut self.freq);
+    }
+}
+
+impl<T> Control for Sine<T>
+where
+    T: 'static,
+{
+    type Ctl<'a> = OscCtl<'a> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut OscCtl {
+            phase: &mut self.phase,
+            freq:  &mut self.freq,
+        });
+    }
 }
 
 /// Wavetable oscillator with linear interpolation
 #[derive(Debug)]
 pub struct Wt<'a, T> {
     pub phase: Fp,
-    pub freq:  Fp,
+    pub freq:  Glide,
     wt:        &'a [T],
 }
 
@@ -79,10 +147,19 @@ impl<'a, T> Wt<'a, T> {
     ) -> Self {
         Self {
             phase: 0.,
-            freq,
+            freq: Glide::from(freq),
             wt,
         }
     }
+
+    /// Wavetable oscillator with frequency in Hz.
+    pub fn hz(
+        wt: &'a [T],
+        freq: Fp,
+        ctx: &Context,
+    ) -> Self {
+        Self::new(wt, ctx.hz(freq))
+    }
 }
 
 impl<'a, T> Node for Wt<'a, T>
@@ -106,10 +183,39 @@ where
             let idx_next = if idx == len - 1 { 0 } else { idx + 1 };
 
             *frm = lin(self.wt[idx], self.wt[idx_next], t.to_float());
-            self.phase += self.freq;
+            self.phase += self.freq.tick();
             while self.phase >= 1. {
                 self.phase -= 1.;
             }
         }
     }
+
+    /// Set the phase to zero.
+    fn reset_state(&mut self) {
+        self.phase = 0.;
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visit_param(visitor, "freq", &mut self.freq);
+    }
+}
+
+impl<T> Control for Wt<'static, T>
+where
+    T: 'static,
+{
+    type Ctl<'a> = OscCtl<'a> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut OscCtl {
+            phase: &mut self.phase,
+            freq:  &mut self.freq,
+        });
+    }
 }
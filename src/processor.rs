@@ -0,0 +1,329 @@
+//! Processing with distinct input and output frames.
+
+use std::marker::PhantomData;
+
+use crate::{
+    control::Visit,
+    frame::{
+        Arf,
+        Frame,
+    },
+    node::{
+        Node,
+        CHUNK,
+    },
+    num::{
+        zero,
+        Float,
+        Fp,
+        Real,
+    },
+};
+
+/// Generalization of [`Node`] to different input and output frames.
+///
+/// Besides the main input, a processor can take an auxiliary input, e.g.
+/// a sidechain signal.  Unlike nodes, processors do not work in place.
+pub trait Processor {
+    type Input: Frame;
+    type Output: Frame;
+    type Aux: Frame;
+
+    /// Process `input` into `output` of the same length.
+    ///
+    /// `aux` is either empty, if there is no auxiliary signal, or of the
+    /// same length as `input`.
+    fn process(
+        &mut self,
+        input: &[Self::Input],
+        aux: &[Self::Aux],
+        output: &mut [Self::Output],
+    );
+
+    /// Prepare for processing.  See [`Node::prepare`].
+    fn prepare(
+        &mut self,
+        _max_block: usize,
+        _sample_rate: Fp,
+    ) {
+    }
+
+    /// Number of frames by which the output is delayed.  See
+    /// [`Node::latency`].
+    fn latency(&self) -> usize {
+        0
+    }
+
+    /// Clear internal state.  See [`Node::reset_state`].
+    fn reset_state(&mut self) {}
+
+    /// Pass parameters to `visitor`.  See [`Node::visit_params`].
+    fn visit_params(
+        &mut self,
+        _visitor: &mut dyn Visit,
+    ) {
+    }
+}
+
+/// Processor that runs a [`Node`] on a copy of the input.
+///
+/// The auxiliary input is ignored.
+#[derive(Debug, Clone)]
+pub struct NodeProcessor<N> {
+    node: N,
+}
+
+impl<N> NodeProcessor<N> {
+    pub fn new(node: N) -> Self {
+        Self {
+            node,
+        }
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    pub fn into_node(self) -> N {
+        self.node
+    }
+}
+
+impl<N> From<N> for NodeProcessor<N>
+where
+    N: Node,
+{
+    fn from(value: N) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<N> Processor for NodeProcessor<N>
+where
+    N: Node,
+{
+    type Aux = N::Frame;
+    type Input = N::Frame;
+    type Output = N::Frame;
+
+    fn process(
+        &mut self,
+        input: &[Self::Input],
+        _aux: &[Self::Aux],
+        output: &mut [Self::Output],
+    ) {
+        output.copy_from_slice(input);
+        self.node.proc(output);
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.node.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.node.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.node.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.node.visit_params(visitor);
+    }
+}
+
+/// Node that runs a [`Processor`] with the same input and output frames.
+///
+/// The processor gets no auxiliary input.  The input is copied to the stack
+/// in small chunks, so no memory is allocated.
+#[derive(Debug, Clone)]
+pub struct ProcessorNode<P> {
+    processor: P,
+}
+
+impl<P> ProcessorNode<P> {
+    pub fn new(processor: P) -> Self {
+        Self {
+            processor,
+        }
+    }
+
+    pub fn processor(&self) -> &P {
+        &self.processor
+    }
+
+    pub fn processor_mut(&mut self) -> &mut P {
+        &mut self.processor
+    }
+
+    pub fn into_processor(self) -> P {
+        self.processor
+    }
+}
+
+impl<P, T> Node for ProcessorNode<P>
+where
+    P: Processor<Input = T, Output = T>,
+    T: Frame,
+{
+    type Frame = T;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let mut buf = [zero::<T>(); CHUNK];
+        for chunk in frames.chunks_mut(CHUNK) {
+            let input = &mut buf[..chunk.len()];
+            input.copy_from_slice(chunk);
+            self.processor.process(input, &[], chunk);
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.processor.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.processor.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.processor.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.processor.visit_params(visitor);
+    }
+}
+
+/// Channel count conversion.
+///
+/// Output channel `j` is the average of input channels `i` with
+/// `i % m == j`, where `m` is the number of output channels, if there are
+/// fewer outputs than inputs.  Otherwise, it is a copy of input channel
+/// `j % n`, where `n` is the number of inputs.  In particular, mono is
+/// spread to all channels and downmixed to mono as the average of all
+/// channels.
+#[derive(Debug, Clone)]
+pub struct Remix<I, O> {
+    _marker: PhantomData<(I, O)>,
+}
+
+impl<I, O> Remix<I, O> {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I, O> Default for Remix<I, O> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, O> Processor for Remix<I, O>
+where
+    I: Frame,
+    O: Frame,
+{
+    type Aux = I;
+    type Input = I;
+    type Output = O;
+
+    #[allow(clippy::cast_precision_loss)]
+    fn process(
+        &mut self,
+        input: &[Self::Input],
+        _aux: &[Self::Aux],
+        output: &mut [Self::Output],
+    ) {
+        for (x, y) in input.iter().zip(output.iter_mut()) {
+            let x = x.as_slice();
+            let y = y.as_mut_slice();
+            let (n, m) = (x.len(), y.len());
+            if n > m {
+                for (j, s) in y.iter_mut().enumerate() {
+                    let chans = x.iter().skip(j).step_by(m);
+                    let count = chans.len();
+                    let sum: f64 = chans.map(|s| s.to_f64()).sum();
+                    *s = O::Sample::from_f64(sum / count as f64);
+                }
+            } else {
+                for (j, s) in y.iter_mut().enumerate() {
+                    *s = x[j % n].to_float();
+                }
+            }
+        }
+    }
+}
+
+/// Equal-power panner from mono to stereo.
+///
+/// `pan` goes from `-1.` (left) to `1.` (right).
+#[derive(Debug, Clone)]
+pub struct Pan<T> {
+    pub pan: Fp,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Pan<T> {
+    #[must_use]
+    pub fn new(pan: Fp) -> Self {
+        Self {
+            pan,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for Pan<T> {
+    fn default() -> Self {
+        Self::new(0.)
+    }
+}
+
+impl<T> Processor for Pan<T>
+where
+    T: Float,
+{
+    type Aux = Arf<T, 1>;
+    type Input = Arf<T, 1>;
+    type Output = Arf<T, 2>;
+
+    fn process(
+        &mut self,
+        input: &[Self::Input],
+        _aux: &[Self::Aux],
+        output: &mut [Self::Output],
+    ) {
+        let theta = (self.pan.clamp(-1., 1.) + 1.) * Fp::PI * 0.25;
+        let left: T = theta.cos().to_float();
+        let right: T = theta.sin().to_float();
+        for (x, y) in input.iter().zip(output.iter_mut()) {
+            *y = [x[0] * left, x[0] * right].into();
+        }
+    }
+}
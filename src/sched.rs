@@ -0,0 +1,370 @@
+//! Sample-accurate scheduling of control changes and control-rate drivers.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    fmt::Debug,
+};
+
+use crate::{
+    control::{
+        Control,
+        Visit,
+    },
+    node::Node,
+    num::Fp,
+};
+
+/// Node that applies timestamped events exactly at their frame.
+///
+/// Events are kept sorted by time, in a buffer allocated up front.  Each
+/// call to [`proc`](Node::proc) is split at event offsets: the node
+/// processes the frames before the event, then the event is applied with
+/// `func` through the node's [`Control`] handle.  Events scheduled at the
+/// same time are applied in order of scheduling.  Events in the past are
+/// applied at the beginning of the next block.
+///
+/// Time is measured in frames, like [`Context::position`], and starts at
+/// zero.
+///
+/// [`Context::position`]: crate::Context::position
+pub struct Scheduler<N, E, F, A = Global>
+where
+    A: Allocator,
+{
+    node:     N,
+    func:     F,
+    // sorted by time in descending order: the next event is the last one
+    events:   Vec<(u64, E), A>,
+    position: u64,
+}
+
+impl<N, E, F, A> Scheduler<N, E, F, A>
+where
+    A: Allocator,
+    N: Control,
+    F: FnMut(&mut N::Ctl<'_>, E),
+{
+    /// Scheduler with room for `capacity` pending events.
+    pub fn new_in(
+        node: N,
+        capacity: usize,
+        func: F,
+        alloc: A,
+    ) -> Self {
+        Self {
+            node,
+            func,
+            events: Vec::with_capacity_in(capacity, alloc),
+            position: 0,
+        }
+    }
+
+    /// Schedule `event` at frame `time`.
+    ///
+    /// Never allocates.
+    ///
+    /// # Errors
+    ///
+    /// Returns the event, if there is no room left.
+    pub fn schedule(
+        &mut self,
+        time: u64,
+        event: E,
+    ) -> Result<(), E> {
+        if self.events.len() == self.events.capacity() {
+            return Err(event);
+        }
+        let index = self.events.partition_point(|(t, _)| *t > time);
+        self.events.insert(index, (time, event));
+        Ok(())
+    }
+
+    /// Apply all pending events now, in order.
+    pub fn flush(&mut self) {
+        while let Some((_, event)) = self.events.pop() {
+            self.apply(event);
+        }
+    }
+
+    fn apply(
+        &mut self,
+        event: E,
+    ) {
+        let func = &mut self.func;
+        self.node.control(|ctl| func(ctl, event));
+    }
+}
+
+impl<N, E, F, A> Scheduler<N, E, F, A>
+where
+    A: Allocator,
+{
+    /// Time of the next processed frame.
+    #[must_use]
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+
+    /// Move to `position`, e.g. after the transport jumped.
+    ///
+    /// Pending events are kept.
+    pub fn set_position(
+        &mut self,
+        position: u64,
+    ) {
+        self.position = position;
+    }
+
+    /// Time of the next pending event.
+    #[must_use]
+    pub fn next_time(&self) -> Option<u64> {
+        self.events.last().map(|(t, _)| *t)
+    }
+
+    /// Number of pending events.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.events.capacity()
+    }
+
+    /// Discard all pending events.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    pub fn into_node(self) -> N {
+        self.node
+    }
+}
+
+impl<N, E, F> Scheduler<N, E, F>
+where
+    N: Control,
+    F: FnMut(&mut N::Ctl<'_>, E),
+{
+    /// Allocates memory on the heap
+    pub fn new(
+        node: N,
+        capacity: usize,
+        func: F,
+    ) -> Self {
+        Self::new_in(node, capacity, func, Global)
+    }
+}
+
+impl<N, E, F, A> Node for Scheduler<N, E, F, A>
+where
+    A: Allocator,
+    N: Node + Control,
+    F: FnMut(&mut N::Ctl<'_>, E),
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let mut start = 0;
+        while let Some(time) = self.next_time() {
+            let Ok(offset) =
+                usize::try_from(time.saturating_sub(self.position))
+            else {
+                break;
+            };
+            if offset >= frames.len() {
+                break;
+            }
+            if offset > start {
+                self.node.proc(&mut frames[start..offset]);
+                start = offset;
+            }
+            let (_, event) = self.events.pop().expect("next event");
+            self.apply(event);
+        }
+        self.node.proc(&mut frames[start..]);
+        self.position += frames.len() as u64;
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.node.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.node.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.node.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.node.visit_params(visitor);
+    }
+}
+
+impl<N, E, F, A> Debug for Scheduler<N, E, F, A>
+where
+    A: Allocator,
+    N: Debug,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("node", &self.node)
+            .field("position", &self.position)
+            .field("pending", &self.events.len())
+            .field("capacity", &self.events.capacity())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Node that runs a control callback at a control rate.
+///
+/// `func` is called through the node's [`Control`] handle before the first
+/// frame and then again after the number of frames it returned, so the
+/// control period can vary from call to call.  The node processes the
+/// frames in between.  A returned period of zero is treated as one frame.
+///
+/// For a fixed period `n`, return `n` from every call.
+pub struct Driver<N, F> {
+    node:   N,
+    func:   F,
+    // frames left until the next call to `func`
+    remain: usize,
+}
+
+impl<N, F> Driver<N, F>
+where
+    N: Control,
+    F: FnMut(&mut N::Ctl<'_>) -> usize,
+{
+    pub fn new(
+        node: N,
+        func: F,
+    ) -> Self {
+        Self {
+            node,
+            func,
+            remain: 0,
+        }
+    }
+
+    /// Frames left until the next call to the control callback.
+    #[must_use]
+    pub fn remain(&self) -> usize {
+        self.remain
+    }
+
+    /// Call the control callback before the next processed frame.
+    pub fn restart(&mut self) {
+        self.remain = 0;
+    }
+
+    pub fn node(&self) -> &N {
+        &self.node
+    }
+
+    pub fn node_mut(&mut self) -> &mut N {
+        &mut self.node
+    }
+
+    pub fn into_node(self) -> N {
+        self.node
+    }
+}
+
+impl<N, F> Node for Driver<N, F>
+where
+    N: Node + Control,
+    F: FnMut(&mut N::Ctl<'_>) -> usize,
+{
+    type Frame = N::Frame;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        let mut frames = frames;
+        while !frames.is_empty() {
+            if self.remain == 0 {
+                let func = &mut self.func;
+                let mut period = 0;
+                self.node.control(|ctl| period = func(ctl));
+                self.remain = period.max(1);
+            }
+            let len = self.remain.min(frames.len());
+            let (chunk, rest) = frames.split_at_mut(len);
+            self.node.proc(chunk);
+            self.remain -= len;
+            frames = rest;
+        }
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.node.prepare(max_block, sample_rate);
+    }
+
+    fn latency(&self) -> usize {
+        self.node.latency()
+    }
+
+    fn reset_state(&mut self) {
+        self.node.reset_state();
+    }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        self.node.visit_params(visitor);
+    }
+}
+
+impl<N, F> Debug for Driver<N, F>
+where
+    N: Debug,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Driver")
+            .field("node", &self.node)
+            .field("remain", &self.remain)
+            .finish_non_exhaustive()
+    }
+}
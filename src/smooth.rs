@@ -0,0 +1,294 @@
+//! Smoothed parameters.
+//!
+//! Changing a parameter abruptly causes audible clicks (zipper noise).
+//! A smoothed parameter moves from its current value towards a target, one
+//! frame at a time.
+
+use crate::{
+    control::Visit,
+    num::Fp,
+};
+
+/// Parameter moving towards a target value.
+pub trait Smooth {
+    /// Current value.
+    fn value(&self) -> Fp;
+
+    fn target(&self) -> Fp;
+
+    /// Start moving towards `target`.
+    fn set(
+        &mut self,
+        target: Fp,
+    );
+
+    /// Set the value and the target at once.
+    fn jump(
+        &mut self,
+        value: Fp,
+    );
+
+    /// Advance by one frame and return the new value.
+    fn tick(&mut self) -> Fp;
+
+    /// Whether the target has been reached.
+    fn is_settled(&self) -> bool {
+        self.value() == self.target()
+    }
+
+    /// Jump to the target.
+    fn finish(&mut self) {
+        self.jump(self.target());
+    }
+}
+
+/// Linear ramp with a fixed rate.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Slew {
+    value:    Fp,
+    target:   Fp,
+    /// Maximal change per frame.
+    pub rate: Fp,
+}
+
+impl Slew {
+    #[must_use]
+    pub fn new(
+        value: Fp,
+        rate: Fp,
+    ) -> Self {
+        Self {
+            value,
+            target: value,
+            rate,
+        }
+    }
+}
+
+impl Smooth for Slew {
+    fn value(&self) -> Fp {
+        self.value
+    }
+
+    fn target(&self) -> Fp {
+        self.target
+    }
+
+    fn set(
+        &mut self,
+        target: Fp,
+    ) {
+        self.target = target;
+    }
+
+    fn jump(
+        &mut self,
+        value: Fp,
+    ) {
+        self.value = value;
+        self.target = value;
+    }
+
+    fn tick(&mut self) -> Fp {
+        let diff = self.target - self.value;
+        if diff.abs() <= self.rate {
+            self.value = self.target;
+        } else {
+            self.value += self.rate.copysign(diff);
+        }
+        self.value
+    }
+}
+
+// Distance to the target, relative to its magnitude, at which exponential
+// smoothing snaps to the target.
+const SETTLE: Fp = 1e-6;
+
+/// One-pole exponential smoothing.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Expo {
+    value:  Fp,
+    target: Fp,
+    coef:   Fp,
+}
+
+impl Expo {
+    /// Smoothing with time constant of `frames`, i.e. the distance to the
+    /// target shrinks by a factor of _e_ every `frames` frames.
+    #[must_use]
+    pub fn new(
+        value: Fp,
+        frames: Fp,
+    ) -> Self {
+        let mut expo = Self {
+            value,
+            target: value,
+            coef: 1.,
+        };
+        expo.set_time(frames);
+        expo
+    }
+
+    /// Set the time constant in frames.  Non-positive time disables
+    /// smoothing.
+    pub fn set_time(
+        &mut self,
+        frames: Fp,
+    ) {
+        self.coef = if frames > 0. {
+            1. - (-1. / frames).exp()
+        } else {
+            1.
+        };
+    }
+}
+
+impl Smooth for Expo {
+    fn value(&self) -> Fp {
+        self.value
+    }
+
+    fn target(&self) -> Fp {
+        self.target
+    }
+
+    fn set(
+        &mut self,
+        target: Fp,
+    ) {
+        self.target = target;
+    }
+
+    fn jump(
+        &mut self,
+        value: Fp,
+    ) {
+        self.value = value;
+        self.target = value;
+    }
+
+    fn tick(&mut self) -> Fp {
+        let diff = self.target - self.value;
+        if diff.abs() <= SETTLE * self.target.abs().max(1.) {
+            self.value = self.target;
+        } else {
+            self.value += diff * self.coef;
+        }
+        self.value
+    }
+}
+
+/// Linear ramp reaching the target in a fixed number of frames.
+///
+/// With time zero, new targets are reached immediately.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glide {
+    value:  Fp,
+    target: Fp,
+    step:   Fp,
+    remain: usize,
+    time:   usize,
+}
+
+impl Glide {
+    #[must_use]
+    pub fn new(
+        value: Fp,
+        time: usize,
+    ) -> Self {
+        Self {
+            value,
+            target: value,
+            step: 0.,
+            remain: 0,
+            time,
+        }
+    }
+
+    /// Number of frames to reach a new target.
+    #[must_use]
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    /// Set the number of frames to reach a new target.  The current ramp is
+    /// not affected.
+    pub fn set_time(
+        &mut self,
+        time: usize,
+    ) {
+        self.time = time;
+    }
+}
+
+impl From<Fp> for Glide {
+    fn from(value: Fp) -> Self {
+        Self::new(value, 0)
+    }
+}
+
+impl Smooth for Glide {
+    fn value(&self) -> Fp {
+        self.value
+    }
+
+    fn target(&self) -> Fp {
+        self.target
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    fn set(
+        &mut self,
+        target: Fp,
+    ) {
+        self.target = target;
+        if self.time == 0 {
+            self.value = target;
+            self.remain = 0;
+        } else {
+            self.step = (target - self.value) / self.time as Fp;
+            self.remain = self.time;
+        }
+    }
+
+    fn jump(
+        &mut self,
+        value: Fp,
+    ) {
+        self.value = value;
+        self.target = value;
+        self.remain = 0;
+    }
+
+    fn tick(&mut self) -> Fp {
+        if self.remain > 0 {
+            self.remain -= 1;
+            self.value = if self.remain == 0 {
+                self.target
+            } else {
+                self.value + self.step
+            };
+        }
+        self.value
+    }
+
+    fn is_settled(&self) -> bool {
+        self.remain == 0
+    }
+}
+
+// Pass the target of `param` to `visitor`, and glide to the new one, if
+// changed.
+pub(crate) fn visit_param<S>(
+    visitor: &mut dyn Visit,
+    name: &str,
+    param: &mut S,
+) where
+    S: Smooth,
+{
+    let mut target = param.target();
+    visitor.param(name, &mut target);
+    if target != param.target() {
+        param.set(target);
+    }
+}
@@ -0,0 +1,392 @@
+//! Lock-free primitives for talking to the audio thread.
+
+use std::{
+    alloc::{
+        Allocator,
+        Global,
+    },
+    cell::UnsafeCell,
+    fmt::Debug,
+    mem::MaybeUninit,
+    sync::{
+        atomic::{
+            AtomicUsize,
+            Ordering,
+        },
+        Arc,
+    },
+};
+
+// Ring buffer shared by one producer and one consumer.  Positions are
+// counters that only grow: `tail - head` is the number of queued items.
+struct Ring<T, A>
+where
+    A: Allocator,
+{
+    slots: Box<[UnsafeCell<MaybeUninit<T>>], A>,
+    head:  AtomicUsize,
+    tail:  AtomicUsize,
+}
+
+// SAFETY: Each slot is accessed either by the producer (before publishing
+// it with `tail`) or by the consumer (before releasing it with `head`),
+// never by both.
+unsafe impl<T: Send, A: Allocator + Send> Send for Ring<T, A> {}
+unsafe impl<T: Send, A: Allocator + Sync> Sync for Ring<T, A> {}
+
+impl<T, A> Ring<T, A>
+where
+    A: Allocator,
+{
+    fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn len(&self) -> usize {
+        // Load `head` first: `tail` can only move away from it
+        let head = self.head.load(Ordering::Acquire);
+        let tail = self.tail.load(Ordering::Acquire);
+        tail.wrapping_sub(head)
+    }
+}
+
+impl<T, A> Drop for Ring<T, A>
+where
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        let head = *self.head.get_mut();
+        let tail = *self.tail.get_mut();
+        let mut pos = head;
+        while pos != tail {
+            let slot = self.slots[pos % self.slots.len()].get_mut();
+            // SAFETY: Slots between head and tail are initialized
+            unsafe { slot.assume_init_drop() };
+            pos = pos.wrapping_add(1);
+        }
+    }
+}
+
+/// Sending half of a single-producer, single-consumer channel.
+pub struct Sender<T, A = Global>
+where
+    A: Allocator,
+{
+    ring: Arc<Ring<T, A>, A>,
+}
+
+/// Receiving half of a single-producer, single-consumer channel.
+pub struct Receiver<T, A = Global>
+where
+    A: Allocator,
+{
+    ring: Arc<Ring<T, A>, A>,
+}
+
+/// Create a bounded, lock-free channel with room for `capacity` items.
+///
+/// Memory is allocated only here: sending and receiving never allocates
+/// or blocks.
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+pub fn spsc_in<T, A>(
+    capacity: usize,
+    alloc: A,
+) -> (Sender<T, A>, Receiver<T, A>)
+where
+    A: Allocator + Clone,
+{
+    assert!(capacity > 0, "capacity must be positive");
+    let mut slots = Box::new_uninit_slice_in(capacity, alloc.clone());
+    for slot in slots.iter_mut() {
+        slot.write(UnsafeCell::new(MaybeUninit::uninit()));
+    }
+    let ring = Arc::new_in(
+        Ring {
+            // SAFETY: All allocated memory has just been initialized
+            slots: unsafe { slots.assume_init() },
+            head:  AtomicUsize::new(0),
+            tail:  AtomicUsize::new(0),
+        },
+        alloc,
+    );
+    (
+        Sender {
+            ring: Arc::clone(&ring),
+        },
+        Receiver {
+            ring,
+        },
+    )
+}
+
+/// Create a bounded, lock-free channel on the heap.
+///
+/// # Panics
+///
+/// Panics if `capacity == 0`.
+#[must_use]
+pub fn spsc<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    spsc_in(capacity, Global)
+}
+
+impl<T, A> Sender<T, A>
+where
+    A: Allocator,
+{
+    /// Send a value, or give it back if the channel is full.
+    ///
+    /// # Errors
+    ///
+    /// Returns the value, if there is no room left in the channel.
+    pub fn send(
+        &mut self,
+        value: T,
+    ) -> Result<(), T> {
+        let ring = &*self.ring;
+        let tail = ring.tail.load(Ordering::Relaxed);
+        let head = ring.head.load(Ordering::Acquire);
+        if tail.wrapping_sub(head) == ring.capacity() {
+            return Err(value);
+        }
+        // SAFETY: The slot is free and only the producer writes to it
+        unsafe {
+            (*ring.slots[tail % ring.capacity()].get()).write(value);
+        }
+        ring.tail.store(tail.wrapping_add(1), Ordering::Release);
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    /// Number of items not yet received.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+}
+
+impl<T, A> Receiver<T, A>
+where
+    A: Allocator,
+{
+    /// Receive the oldest value, if any.
+    pub fn recv(&mut self) -> Option<T> {
+        let ring = &*self.ring;
+        let head = ring.head.load(Ordering::Relaxed);
+        let tail = ring.tail.load(Ordering::Acquire);
+        if head == tail {
+            return None;
+        }
+        // SAFETY: The slot has been published by the producer and only the
+        // consumer reads from it
+        let value = unsafe {
+            (*ring.slots[head % ring.capacity()].get()).assume_init_read()
+        };
+        ring.head.store(head.wrapping_add(1), Ordering::Release);
+        Some(value)
+    }
+
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.ring.capacity()
+    }
+
+    /// Number of items waiting to be received.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ring.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T, A> Debug for Sender<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Sender")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+impl<T, A> Debug for Receiver<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("Receiver")
+            .field("len", &self.len())
+            .field("capacity", &self.capacity())
+            .finish()
+    }
+}
+
+// Index of the back slot in the lower bits of `TripleShared::back`, and a
+// flag telling whether it holds a value newer than the one read.
+const TRIPLE_INDEX: usize = 0b11;
+const TRIPLE_DIRTY: usize = 0b100;
+
+// Three slots: one owned by the writer, one by the reader, and the back
+// one, exchanged atomically.
+struct TripleShared<T> {
+    slots: [UnsafeCell<T>; 3],
+    back:  AtomicUsize,
+}
+
+// SAFETY: Each slot is owned either by the writer, by the reader, or by
+// `back`, and ownership is exchanged only through `back`.
+unsafe impl<T: Send> Send for TripleShared<T> {}
+unsafe impl<T: Send> Sync for TripleShared<T> {}
+
+/// Writing half of a triple buffer.
+pub struct TripleWriter<T, A = Global>
+where
+    A: Allocator,
+{
+    shared: Arc<TripleShared<T>, A>,
+    index:  usize,
+}
+
+/// Reading half of a triple buffer.
+pub struct TripleReader<T, A = Global>
+where
+    A: Allocator,
+{
+    shared: Arc<TripleShared<T>, A>,
+    index:  usize,
+}
+
+/// Create a lock-free triple buffer for publishing the latest value.
+///
+/// The writer updates its own copy in place and publishes it; the reader
+/// always gets the latest published value.  Neither side ever blocks or
+/// allocates, and intermediate values may be skipped.  The three copies
+/// are created with `init`.
+pub fn triple_in<T, A>(
+    mut init: impl FnMut() -> T,
+    alloc: A,
+) -> (TripleWriter<T, A>, TripleReader<T, A>)
+where
+    A: Allocator + Clone,
+{
+    let shared = Arc::new_in(
+        TripleShared {
+            slots: [(); 3].map(|()| UnsafeCell::new(init())),
+            back:  AtomicUsize::new(1),
+        },
+        alloc,
+    );
+    (
+        TripleWriter {
+            shared: Arc::clone(&shared),
+            index:  0,
+        },
+        TripleReader {
+            shared,
+            index: 2,
+        },
+    )
+}
+
+/// Create a lock-free triple buffer on the heap.
+pub fn triple<T>(
+    init: impl FnMut() -> T
+) -> (TripleWriter<T>, TripleReader<T>) {
+    triple_in(init, Global)
+}
+
+impl<T, A> TripleWriter<T, A>
+where
+    A: Allocator,
+{
+    /// Value to be published next.  It holds an older value, not
+    /// necessarily the last published one.
+    pub fn write(&mut self) -> &mut T {
+        // SAFETY: The slot is owned by the writer
+        unsafe { &mut *self.shared.slots[self.index].get() }
+    }
+
+    /// Publish the value written, making it the latest one.
+    pub fn publish(&mut self) {
+        let back = self
+            .shared
+            .back
+            .swap(self.index | TRIPLE_DIRTY, Ordering::AcqRel);
+        self.index = back & TRIPLE_INDEX;
+    }
+}
+
+impl<T, A> TripleReader<T, A>
+where
+    A: Allocator,
+{
+    /// Whether a value newer than the one read has been published.
+    #[must_use]
+    pub fn has_update(&self) -> bool {
+        self.shared.back.load(Ordering::Relaxed) & TRIPLE_DIRTY != 0
+    }
+
+    /// Latest published value.
+    pub fn read(&mut self) -> &T {
+        if self.has_update() {
+            let back = self.shared.back.swap(self.index, Ordering::AcqRel);
+            self.index = back & TRIPLE_INDEX;
+        }
+        // SAFETY: The slot is owned by the reader
+        unsafe { &*self.shared.slots[self.index].get() }
+    }
+}
+
+impl<T, A> Debug for TripleWriter<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("TripleWriter").finish_non_exhaustive()
+    }
+}
+
+impl<T, A> Debug for TripleReader<T, A>
+where
+    A: Allocator,
+{
+    fn fmt(
+        &self,
+        f: &mut std::fmt::Formatter<'_>,
+    ) -> std::fmt::Result {
+        f.debug_struct("TripleReader")
+            .field("has_update", &self.has_update())
+            .finish_non_exhaustive()
+    }
+}
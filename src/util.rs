@@ -1,6 +1,11 @@
 use std::marker::PhantomData;
 
 use crate::{
+    control::{
+        Control,
+        Reset,
+        Visit,
+    },
     frame::Frame,
     node::Node,
     num::{
@@ -8,14 +13,22 @@ use crate::{
         Float,
         Fp,
     },
+    smooth::{
+        visit_param,
+        Glide,
+        Smooth,
+    },
 };
 
+/// Gain stage.
+///
+/// Changes of gain glide over [`Glide::time`] frames, instantly by default.
 #[derive(Debug, Clone)]
 pub struct Util<T>
 where
     T: Frame,
 {
-    pub gain: Fp,
+    pub gain: Glide,
     _marker:  PhantomData<T>,
 }
 
@@ -26,7 +39,7 @@ where
     #[must_use]
     pub fn new() -> Self {
         Self {
-            gain:    one(),
+            gain:    Glide::from(one::<Fp>()),
             _marker: PhantomData,
         }
     }
@@ -52,7 +65,42 @@ where
         frames: &mut [Self::Frame],
     ) {
         for frm in frames {
-            *frm *= self.gain.to_float();
+            *frm *= self.gain.tick().to_float();
         }
     }
+
+    fn visit_params(
+        &mut self,
+        visitor: &mut dyn Visit,
+    ) {
+        visit_param(visitor, "gain", &mut self.gain);
+    }
+}
+
+/// Handle to the gain of [`Util`].
+///
+/// `Util` has no state: reset does nothing, the glide of gain goes on.
+#[derive(Debug)]
+pub struct UtilCtl<'a> {
+    pub gain: &'a mut Glide,
+}
+
+impl<'a> Reset for UtilCtl<'a> {
+    fn reset(&mut self) {}
+}
+
+impl<T> Control for Util<T>
+where
+    T: Frame + 'static,
+{
+    type Ctl<'a> = UtilCtl<'a> where Self: 'a;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut UtilCtl {
+            gain: &mut self.gain,
+        });
+    }
 }
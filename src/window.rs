@@ -0,0 +1,108 @@
+//! Window functions.
+
+use crate::num::{
+    Fp,
+    Real,
+};
+
+/// Window function.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Window {
+    Rectangular,
+    #[default]
+    Hann,
+    Hamming,
+    Blackman,
+    /// Kaiser window with shape parameter `beta`.  Higher `beta` widens the
+    /// main lobe and lowers the side lobes.
+    Kaiser(Fp),
+}
+
+impl Window {
+    /// Value at `n` of the symmetric window of `len` points.
+    ///
+    /// Symmetric windows are used to design filters.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn value(
+        self,
+        n: usize,
+        len: usize,
+    ) -> Fp {
+        if len < 2 {
+            return 1.;
+        }
+        self.at(n as Fp / (len - 1) as Fp)
+    }
+
+    /// Fill `out` with the symmetric window.
+    pub fn fill(
+        self,
+        out: &mut [Fp],
+    ) {
+        let len = out.len();
+        for (n, x) in out.iter_mut().enumerate() {
+            *x = self.value(n, len);
+        }
+    }
+
+    /// Value at `n` of the periodic window of `len` points.
+    ///
+    /// Periodic windows are the symmetric ones of `len + 1` points without
+    /// the last point.  They are used for spectral analysis, as their
+    /// shifted copies overlap-add to a constant.
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn value_periodic(
+        self,
+        n: usize,
+        len: usize,
+    ) -> Fp {
+        self.at(n as Fp / len as Fp)
+    }
+
+    /// Fill `out` with the periodic window.
+    pub fn fill_periodic(
+        self,
+        out: &mut [Fp],
+    ) {
+        let len = out.len();
+        for (n, x) in out.iter_mut().enumerate() {
+            *x = self.value_periodic(n, len);
+        }
+    }
+
+    // Value at `x` in `[0, 1]`.
+    fn at(
+        self,
+        x: Fp,
+    ) -> Fp {
+        let cos = |k: Fp| <Fp as Real>::cos(k * <Fp as Real>::TAU * x);
+        match self {
+            Self::Rectangular => 1.,
+            Self::Hann => 0.5 - 0.5 * cos(1.),
+            Self::Hamming => 0.54 - 0.46 * cos(1.),
+            Self::Blackman => 0.42 - 0.5 * cos(1.) + 0.08 * cos(2.),
+            Self::Kaiser(beta) => {
+                let t = 2. * x - 1.;
+                bessel_i0(beta * (1. - t * t).max(0.).sqrt()) / bessel_i0(beta)
+            }
+        }
+    }
+}
+
+// Modified Bessel function of the first kind of order zero.
+#[allow(clippy::cast_precision_loss)]
+fn bessel_i0(x: Fp) -> Fp {
+    let half = x * 0.5;
+    let mut term: Fp = 1.;
+    let mut sum: Fp = 1.;
+    let mut k = 1;
+    while term > sum * Fp::EPSILON {
+        let r = half / k as Fp;
+        term *= r * r;
+        sum += term;
+        k += 1;
+    }
+    sum
+}
@@ -0,0 +1,173 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    analyzer::{
+        Analyzer,
+        Averaging,
+        Binning,
+    },
+    frame::{
+        splat,
+        Mo,
+        St,
+    },
+    node::Node,
+    num::{
+        Fp,
+        Real,
+    },
+    window::Window,
+};
+
+#[allow(clippy::cast_precision_loss)]
+fn sine(
+    len: usize,
+    freq: Fp,
+    amp: Fp,
+) -> Vec<Mo> {
+    (0..len)
+        .map(|n| splat(amp * (<Fp as Real>::TAU * freq * n as Fp).sin()))
+        .collect()
+}
+
+fn argmax(x: &[Fp]) -> usize {
+    (0..x.len()).fold(0, |m, k| if x[k] > x[m] { k } else { m })
+}
+
+#[test]
+fn check_analyzer_01() {
+    let (mut analyzer, _reader) =
+        Analyzer::new(64, 16, Window::Hann, Binning::Linear, Averaging::Off);
+    let input = sine(200, 0.1, 0.5);
+    let mut frames = input.clone();
+    analyzer.proc(&mut frames);
+    assert_eq!(frames, input);
+    assert_eq!(analyzer.latency(), 0);
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn check_analyzer_amplitude_01() {
+    let (mut analyzer, mut reader) =
+        Analyzer::new(256, 64, Window::Hann, Binning::Linear, Averaging::Off);
+    assert_eq!(analyzer.len(), 129);
+    assert_eq!(reader.read().count(), 0);
+
+    let bin = 32;
+    analyzer.proc(&mut sine(1024, bin as Fp / 256., 0.5));
+    let spectrum = reader.read();
+    assert_eq!(spectrum.count(), 16);
+    assert_eq!(argmax(spectrum.magnitudes()), bin);
+    assert_near(spectrum.magnitudes()[bin], 0.5, 1e-9);
+    assert_near(spectrum.freqs()[bin], 0.125, 1e-12);
+}
+
+#[test]
+fn check_analyzer_channels_01() {
+    let (mut analyzer, mut reader) =
+        Analyzer::new(64, 64, Window::Hann, Binning::Linear, Averaging::Off);
+    let mut frames: Vec<St> = sine(64, 0.25, 1.)
+        .into_iter()
+        .map(|x| [<[Fp; 1]>::from(x)[0], 0.].into())
+        .collect();
+    analyzer.proc(&mut frames);
+    assert_near(reader.read().magnitudes()[16], 0.5, 1e-9);
+}
+
+#[test]
+fn check_analyzer_log_01() {
+    let bands = 24;
+    let (mut analyzer, mut reader) = Analyzer::new(
+        1024,
+        256,
+        Window::Blackman,
+        Binning::Log {
+            bands,
+            min_freq: 1e-3,
+        },
+        Averaging::Off,
+    );
+    assert_eq!(analyzer.len(), bands);
+    // centered on a bin of the transform
+    let freq = 51. / 1024.;
+    analyzer.proc(&mut sine(4096, freq, 1.));
+    let spectrum = reader.read();
+    assert_eq!(spectrum.len(), bands);
+
+    let freqs = spectrum.freqs();
+    assert!(freqs.windows(2).all(|w| w[0] < w[1]));
+    assert!(freqs[0] > 1e-3 && freqs[bands - 1] < 0.5);
+    // the band holding the peak has the closest center in log scale
+    let k = argmax(spectrum.magnitudes());
+    let dist = |f: Fp| (f / freq).ln().abs();
+    assert!((0..bands).all(|i| dist(freqs[k]) <= dist(freqs[i])));
+    assert_near(spectrum.magnitudes()[k], 1., 1e-2);
+}
+
+#[test]
+fn check_analyzer_peak_hold_01() {
+    let decay = 0.5;
+    let (mut analyzer, mut reader) = Analyzer::new(
+        64,
+        64,
+        Window::Hann,
+        Binning::Linear,
+        Averaging::PeakHold {
+            decay,
+        },
+    );
+    analyzer.proc(&mut sine(64, 0.25, 1.));
+    let peak = reader.read().magnitudes()[16];
+    assert_near(peak, 1., 1e-9);
+
+    analyzer.proc(&mut [splat::<Mo>(0.); 64]);
+    assert_near(reader.read().magnitudes()[16], peak * decay, 1e-9);
+    analyzer.proc(&mut [splat::<Mo>(0.); 64]);
+    assert_near(reader.read().magnitudes()[16], peak * decay * decay, 1e-9);
+
+    // a louder peak is taken at once
+    analyzer.proc(&mut sine(64, 0.25, 2.));
+    assert_near(reader.read().magnitudes()[16], 2., 1e-9);
+}
+
+#[test]
+fn check_analyzer_exponential_01() {
+    let decay = 0.5;
+    let (mut analyzer, mut reader) = Analyzer::new(
+        64,
+        64,
+        Window::Hann,
+        Binning::Linear,
+        Averaging::Exponential {
+            decay,
+        },
+    );
+    let mut expected = 0.;
+    for _ in 0..20 {
+        analyzer.proc(&mut sine(64, 0.25, 1.));
+        expected = 1. + decay * (expected - 1.);
+        assert_near(reader.read().magnitudes()[16], expected, 1e-9);
+    }
+    assert_near(expected, 1., 1e-5);
+}
+
+#[test]
+fn check_analyzer_reset_01() {
+    let (mut analyzer, mut reader) = Analyzer::new(
+        64,
+        64,
+        Window::Hann,
+        Binning::Linear,
+        Averaging::PeakHold {
+            decay: 1.
+        },
+    );
+    analyzer.proc(&mut sine(64, 0.25, 1.));
+    analyzer.reset_state();
+    analyzer.proc(&mut [splat::<Mo>(0.); 64]);
+    let spectrum = reader.read();
+    // the count goes on
+    assert_eq!(spectrum.count(), 2);
+    assert!(spectrum.magnitudes().iter().all(|x| *x == 0.));
+}
@@ -0,0 +1,37 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::BiquadCoefs,
+    num::{
+        Fp,
+        Real,
+    },
+};
+
+// Squared magnitude of the response at `freq`.
+fn gain_sqr(
+    c: &BiquadCoefs,
+    freq: Fp,
+) -> Fp {
+    let w = 2. * Fp::PI * freq;
+    let (c1, s1, c2, s2) = (w.cos(), w.sin(), (2. * w).cos(), (2. * w).sin());
+    let num = (c.b0 + c.b1 * c1 + c.b2 * c2).powi(2)
+        + (c.b1 * s1 + c.b2 * s2).powi(2);
+    let den =
+        (1. + c.a1 * c1 + c.a2 * c2).powi(2) + (c.a1 * s1 + c.a2 * s2).powi(2);
+    num / den
+}
+
+// Unity gain at Nyquist, as for the lowpass at DC.
+#[test]
+fn check_butter_hipass_01() {
+    for cutoff in [0.01, 0.1, 0.3] {
+        let c = BiquadCoefs::butter_hipass(cutoff);
+        let dc = (c.b0 + c.b1 + c.b2) / (1. + c.a1 + c.a2);
+        let nyquist = (c.b0 - c.b1 + c.b2) / (1. - c.a1 + c.a2);
+        assert_near(dc, 0., 1e-9);
+        assert_near(nyquist, 1., 1e-9);
+        assert_near(gain_sqr(&c, cutoff), 0.5, 1e-9);
+    }
+}
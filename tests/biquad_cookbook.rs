@@ -0,0 +1,80 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        lowpass,
+        BiquadCoefs,
+    },
+    frame::Mo,
+    num::Fp,
+};
+
+// Gain at frequency zero
+fn dc(c: &BiquadCoefs) -> Fp {
+    (c.b0 + c.b1 + c.b2) / (1. + c.a1 + c.a2)
+}
+
+// Gain at the Nyquist frequency
+fn nyquist(c: &BiquadCoefs) -> Fp {
+    (c.b0 - c.b1 + c.b2) / (1. - c.a1 + c.a2)
+}
+
+#[test]
+fn check_cookbook_pass_01() {
+    let q = Fp::sqrt(0.5);
+    let lp = BiquadCoefs::lowpass(0.1, q);
+    assert_near(dc(&lp), 1., 1e-9);
+    assert_near(nyquist(&lp), 0., 1e-9);
+
+    let butter = BiquadCoefs::butter_lowpass(0.1);
+    assert_near(lp.a1, butter.a1, 1e-9);
+    assert_near(lp.a2, butter.a2, 1e-9);
+    assert_near(lp.b0, butter.b0, 1e-9);
+
+    let hp = BiquadCoefs::hipass(0.1, q);
+    assert_near(dc(&hp), 0., 1e-9);
+    assert_near(nyquist(&hp), 1., 1e-9);
+
+    let bp = BiquadCoefs::bandpass_peak(0.2, 2.);
+    assert_near(dc(&bp), 0., 1e-9);
+    assert_near(nyquist(&bp), 0., 1e-9);
+
+    let bp = BiquadCoefs::bandpass(0.2, 2.);
+    assert_near(dc(&bp), 0., 1e-9);
+    assert!(bp.is_stable());
+}
+
+#[test]
+fn check_cookbook_notch_allpass_01() {
+    let notch = BiquadCoefs::notch(0.25, 1.);
+    assert_near(dc(&notch), 1., 1e-9);
+    assert_near(nyquist(&notch), 1., 1e-9);
+    // zeros on the unit circle at the center frequency
+    assert_near(notch.b1, 0., 1e-9);
+
+    let ap = BiquadCoefs::allpass(0.1, 0.7);
+    assert_near(dc(&ap).abs(), 1., 1e-9);
+    assert_near(nyquist(&ap).abs(), 1., 1e-9);
+    assert_near(ap.b0, ap.a2, 1e-9);
+    assert_near(ap.b2, 1., 1e-9);
+}
+
+#[test]
+fn check_cookbook_eq_01() {
+    let peak = BiquadCoefs::peak(0.1, 1., 6.);
+    assert_near(dc(&peak), 1., 1e-9);
+    assert_near(nyquist(&peak), 1., 1e-9);
+
+    let gain = Fp::powf(10., 6. / 20.);
+    let low = BiquadCoefs::lowshelf(0.1, Fp::sqrt(0.5), 6.);
+    assert_near(dc(&low), gain, 1e-9);
+    assert_near(nyquist(&low), 1., 1e-9);
+
+    let high = BiquadCoefs::hishelf(0.1, Fp::sqrt(0.5), -6.);
+    assert_near(dc(&high), 1., 1e-9);
+    assert_near(nyquist(&high), 1. / gain, 1e-9);
+
+    let biquad = lowpass::<Mo>(0.1, 2.);
+    assert_near(biquad.coefs.a1, BiquadCoefs::lowpass(0.1, 2.).a1, 1e-9);
+}
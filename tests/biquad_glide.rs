@@ -0,0 +1,111 @@
+use sn_dsp::{
+    filter::{
+        butter_lowpass,
+        lpf2,
+        BiquadCoefs,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::{
+        zero,
+        Fp,
+    },
+    sched::Driver,
+    Control,
+};
+
+#[test]
+fn check_biquad_glide_01() {
+    let target = BiquadCoefs::butter_lowpass(0.3);
+    let mut biquad = butter_lowpass::<Mo>(0.01);
+    biquad.glide(target, 4);
+    assert!(biquad.is_gliding());
+
+    let frames = &mut [zero(); 3];
+    biquad.proc(frames);
+    assert!(biquad.is_gliding());
+    assert!((biquad.coefs.a1 - target.a1).abs() > 1e-6);
+
+    biquad.proc(&mut [zero()]);
+    assert!(!biquad.is_gliding());
+    assert_eq!(biquad.coefs.a1, target.a1);
+    assert_eq!(biquad.coefs.b0, target.b0);
+}
+
+#[test]
+fn check_biquad_glide_stable_01() {
+    let from = BiquadCoefs::resonator(0.01, 0.001);
+    let to = BiquadCoefs::butter_hipass(0.45);
+    assert!(from.is_stable());
+    assert!(to.is_stable());
+    assert!(!BiquadCoefs::arbitrary(0., 1.5, 1., 0., 0.).is_stable());
+
+    let mut biquad = butter_lowpass::<Mo>(0.1);
+    biquad.coefs = from;
+    biquad.control(|ctl| ctl.glide(to, 100));
+    for _ in 0..100 {
+        biquad.tick(splat(1.));
+        assert!(biquad.coefs.is_stable());
+    }
+    assert!(!biquad.is_gliding());
+}
+
+#[test]
+fn check_biquad_glide_reset_01() {
+    let target = BiquadCoefs::butter_lowpass(0.3);
+    let mut biquad = butter_lowpass::<Mo>(0.01);
+    biquad.glide(target, 100);
+    biquad.tick(splat(1.));
+    let coefs = biquad.coefs;
+    // parameters are kept
+    biquad.reset_state();
+    assert!(biquad.is_gliding());
+    assert_eq!(biquad.coefs, coefs);
+
+    for _ in 0..99 {
+        biquad.tick(zero());
+    }
+    assert!(!biquad.is_gliding());
+    assert_eq!(biquad.coefs, target);
+}
+
+#[test]
+fn check_biquad_glide_assign_01() {
+    let target = BiquadCoefs::butter_lowpass(0.3);
+    let other = BiquadCoefs::butter_lowpass(0.2);
+    let mut biquad = butter_lowpass::<Mo>(0.01);
+    biquad.glide(target, 4);
+    biquad.tick(zero());
+
+    // direct assignment cancels the glide
+    biquad.coefs = other;
+    assert!(!biquad.is_gliding());
+    biquad.proc(&mut [zero(); 4]);
+    assert_eq!(biquad.coefs, other);
+
+    biquad.glide(target, 4);
+    biquad.control(|ctl| *ctl.coefs = BiquadCoefs::default());
+    assert!(!biquad.is_gliding());
+    biquad.proc(&mut [zero(); 4]);
+    assert_eq!(biquad.coefs, BiquadCoefs::default());
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn check_biquad_sweep_driver_01() {
+    const PERIOD: usize = 16;
+    let mut step = 0;
+    let mut driver = Driver::new(lpf2::<Mo>(0.01), |ctl| {
+        step += 1;
+        ctl.glide_cutoff(0.01 + 0.01 * step as Fp, PERIOD);
+        PERIOD
+    });
+
+    let frames = &mut [splat::<Mo>(1.); 4 * PERIOD];
+    driver.proc(frames);
+    assert_eq!(step, 4);
+    assert!(frames.iter().all(|x| x[0].is_finite()));
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    bus::Bus,
+    frame::{
+        splat,
+        Mo,
+    },
+    node::{
+        Node,
+        StackNode,
+    },
+    num::{
+        Fp,
+        Real,
+    },
+};
+
+fn gain(g: Fp) -> StackNode<Mo, impl FnMut(&mut [Mo])> {
+    StackNode::new(move |frames: &mut [Mo]| {
+        for frm in frames {
+            *frm *= g;
+        }
+    })
+}
+
+#[test]
+fn check_crossfade_01() {
+    let mut bus = Bus::new();
+    bus.node_push(gain(0.));
+    bus.node_push(gain(2.));
+
+    bus.replace_crossfade(0, Box::new(gain(1.)), 4);
+    assert!(bus.is_fading(0));
+
+    let frames = &mut [splat(1.); 6];
+    bus.proc(&mut frames[..3]);
+    bus.proc(&mut frames[3..]);
+
+    for (i, frm) in frames.iter().enumerate() {
+        let t = (i.min(4) as Fp) / 4. * Fp::PI * 0.5;
+        assert_near(frm[0], 2. * t.sin(), 1e-12);
+    }
+    assert!(!bus.is_fading(0));
+}
+
+#[test]
+fn check_crossfade_equal_power_01() {
+    let mut bus = Bus::new();
+    bus.node_push(gain(1.));
+    bus.replace_crossfade(0, Box::new(gain(-1.)), 200);
+
+    // for uncorrelated branches, power is kept
+    let mut old = [splat::<Mo>(1.); 200];
+    let mut new = [splat::<Mo>(1.); 200];
+    bus.proc(&mut old);
+
+    let mut bus = Bus::new();
+    bus.node_push(gain(1.));
+    bus.replace_crossfade(0, Box::new(gain(1.)), 200);
+    bus.proc(&mut new);
+
+    for (a, b) in old.iter().zip(new.iter()) {
+        let (x, y) = ((b[0] + a[0]) / 2., (b[0] - a[0]) / 2.);
+        assert_near(x * x + y * y, 1., 1e-12);
+    }
+}
+
+#[test]
+fn check_crossfade_shift_01() {
+    let mut bus = Bus::new();
+    bus.node_push(gain(0.));
+    bus.replace_crossfade(0, Box::new(gain(1.)), 4);
+
+    bus.insert(0, Box::new(gain(3.)));
+    assert!(!bus.is_fading(0));
+    assert!(bus.is_fading(1));
+
+    let _ = bus.remove(0);
+    assert!(bus.is_fading(0));
+
+    let _ = bus.replace(0, Box::new(gain(1.)));
+    assert!(!bus.is_fading(0));
+
+    let frames = &mut [splat(1.); 2];
+    bus.proc(frames);
+    assert_eq!(frames, &[splat(1.); 2]);
+}
+
+#[test]
+fn check_crossfade_retire_01() {
+    let mut bus = Bus::new();
+    bus.node_push(gain(0.));
+    bus.node_push(gain(1.));
+    bus.replace_crossfade(0, Box::new(gain(1.)), 2);
+    bus.replace_crossfade(1, Box::new(gain(2.)), 4);
+    assert!(bus.take_retired().is_none());
+
+    // finished fades and cancelled ones keep the old node
+    bus.proc(&mut [splat(1.); 2]);
+    assert!(!bus.is_fading(0));
+    let _ = bus.replace(1, Box::new(gain(1.)));
+    assert!(!bus.is_fading(1));
+
+    assert!(bus.take_retired().is_some());
+    assert!(bus.take_retired().is_some());
+    assert!(bus.take_retired().is_none());
+}
@@ -0,0 +1,217 @@
+use std::thread::{
+    self,
+    ThreadId,
+};
+
+use sn_dsp::{
+    bus::{
+        Bus,
+        Remote,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::Fp,
+    sync::spsc,
+};
+
+struct Scale {
+    gain:    Fp,
+    dropped: Option<std::sync::mpsc::Sender<ThreadId>>,
+}
+
+impl Node for Scale {
+    type Frame = Mo;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        for frm in frames {
+            *frm *= self.gain;
+        }
+    }
+}
+
+impl Drop for Scale {
+    fn drop(&mut self) {
+        if let Some(tx) = &self.dropped {
+            tx.send(thread::current().id()).unwrap();
+        }
+    }
+}
+
+fn scale(gain: Fp) -> Box<Scale> {
+    Box::new(Scale {
+        gain,
+        dropped: None,
+    })
+}
+
+#[test]
+fn check_spsc_01() {
+    let (mut tx, mut rx) = spsc(2);
+
+    assert!(rx.recv().is_none());
+    tx.send(1).unwrap();
+    tx.send(2).unwrap();
+    assert_eq!(tx.send(3), Err(3));
+    assert_eq!(rx.recv(), Some(1));
+    tx.send(4).unwrap();
+    assert_eq!(rx.recv(), Some(2));
+    assert_eq!(rx.recv(), Some(4));
+    assert!(rx.recv().is_none());
+}
+
+#[test]
+fn check_spsc_threads_01() {
+    let (mut tx, mut rx) = spsc(4);
+
+    thread::scope(|s| {
+        s.spawn(move || {
+            for i in 0..1000 {
+                let mut value = i;
+                while let Err(v) = tx.send(value) {
+                    value = v;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < 1000 {
+            if let Some(value) = rx.recv() {
+                assert_eq!(value, expected);
+                expected += 1;
+            }
+        }
+    });
+}
+
+#[test]
+fn check_remote_edits_01() {
+    let (mut remote, mut editor) = Remote::new(Bus::new(), 8, 8);
+
+    editor.push(scale(2.)).unwrap();
+    editor.push(scale(3.)).unwrap();
+    editor.insert(0, scale(5.)).unwrap();
+    editor.replace(2, scale(7.)).unwrap();
+    // out of bounds, node is returned
+    editor.insert(9, scale(11.)).unwrap();
+
+    let frames = &mut [splat(1.)];
+    remote.proc(frames);
+
+    assert_eq!(remote.bus().len(), 3);
+    assert_eq!(frames, &[splat(70.)]);
+    assert_eq!(editor.collect(), 2);
+
+    editor.remove(1).unwrap();
+    editor.pop().unwrap();
+
+    let frames = &mut [splat(1.)];
+    remote.proc(frames);
+
+    assert_eq!(frames, &[splat(5.)]);
+    assert_eq!(editor.collect(), 2);
+}
+
+#[test]
+fn check_remote_drop_thread_01() {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut bus = Bus::new();
+    bus.push(Box::new(Scale {
+        gain:    2.,
+        dropped: Some(tx),
+    }));
+    bus.push(scale(3.));
+    let (mut remote, mut editor) = Remote::new(bus, 1, 2);
+
+    editor.remove(0).unwrap();
+    thread::scope(|s| {
+        s.spawn(|| {
+            let frames = &mut [splat(1.)];
+            remote.proc(frames);
+            assert_eq!(frames, &[splat(3.)]);
+        });
+    });
+
+    // trash is full: further edits wait for the editor
+    editor.pop().unwrap();
+    remote.proc(&mut [splat(1.)]);
+    assert_eq!(remote.bus().len(), 1);
+
+    assert_eq!(editor.collect(), 1);
+    assert_eq!(rx.recv().unwrap(), thread::current().id());
+
+    remote.proc(&mut [splat(1.)]);
+    assert_eq!(remote.bus().len(), 0);
+}
+
+#[test]
+fn check_remote_capacity_01() {
+    let (mut remote, mut editor) = Remote::new(Bus::new(), 16, 2);
+    let capacity = remote.bus().capacity();
+    assert!(capacity >= 2);
+
+    for _ in 0..=capacity {
+        editor.push(scale(2.)).unwrap();
+    }
+    remote.proc(&mut [splat(1.)]);
+
+    // the bus does not grow: the extra node is returned
+    assert_eq!(remote.bus().len(), capacity);
+    assert_eq!(remote.bus().capacity(), capacity);
+    assert_eq!(editor.collect(), 1);
+}
+
+#[test]
+fn check_remote_editor_drop_01() {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut bus = Bus::new();
+    bus.push(Box::new(Scale {
+        gain:    2.,
+        dropped: Some(tx),
+    }));
+    let (mut remote, mut editor) = Remote::new(bus, 2, 1);
+
+    editor.pop().unwrap();
+    thread::scope(|s| {
+        s.spawn(|| remote.proc(&mut [splat(1.)]));
+    });
+    assert!(rx.try_recv().is_err());
+
+    // removed nodes are dropped with the editor, on its thread
+    drop(editor);
+    assert_eq!(rx.recv().unwrap(), thread::current().id());
+    assert!(remote.bus().is_empty());
+}
+
+#[test]
+fn check_remote_crossfade_01() {
+    let (tx, rx) = std::sync::mpsc::channel();
+
+    let mut bus = Bus::new();
+    bus.push(Box::new(Scale {
+        gain:    2.,
+        dropped: Some(tx),
+    }));
+    let (mut remote, mut editor) = Remote::new(bus, 2, 1);
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            remote.bus_mut().replace_crossfade(0, scale(3.), 2);
+            remote.proc(&mut [splat(1.); 4]);
+            remote.proc(&mut [splat(1.)]);
+        });
+    });
+    assert!(rx.try_recv().is_err());
+
+    // the faded out node is dropped by the editor
+    assert_eq!(editor.collect(), 1);
+    assert_eq!(rx.recv().unwrap(), thread::current().id());
+}
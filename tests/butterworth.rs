@@ -0,0 +1,140 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        bpf,
+        bpf2,
+        hpf,
+        hpf4,
+        lpf,
+        lpf8,
+        Bpf,
+        Hpf,
+        Response,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::{
+        Fp,
+        Real,
+    },
+    Control,
+};
+
+const MINUS_3DB: Fp = -3.010_299_956_639_812;
+const FREQS: [Fp; 6] = [0.001, 0.02, 0.05, 0.1, 0.2, 0.4];
+
+// Squared magnitude of the prewarped Butterworth response of `order`.
+fn butter_gain_db(
+    ratio: Fp,
+    order: usize,
+) -> Fp {
+    -10. * (1. + ratio.powi(2 * order as i32)).log10()
+}
+
+fn warp(freq: Fp) -> Fp {
+    (Fp::PI * freq).tan()
+}
+
+#[test]
+#[cfg_attr(not(feature = "f64"), ignore = "stopband is below the range of f32")]
+fn check_butter_lpf_hpf_01() {
+    fn check<const N: usize>() {
+        let cutoff = 0.07;
+        let lp = lpf::<N, Mo>(cutoff);
+        let hp = hpf::<N, Mo>(cutoff);
+        assert_near(lp.gain_db(cutoff), MINUS_3DB, 1e-9);
+        assert_near(hp.gain_db(cutoff), MINUS_3DB, 1e-9);
+        for freq in FREQS {
+            let ratio = warp(freq) / warp(cutoff);
+            assert_near(lp.gain_db(freq), butter_gain_db(ratio, 2 * N), 1e-6);
+            assert_near(
+                hp.gain_db(freq),
+                butter_gain_db(ratio.recip(), 2 * N),
+                1e-6,
+            );
+        }
+    }
+    check::<1>();
+    check::<2>();
+    check::<3>();
+    check::<4>();
+    check::<8>();
+}
+
+#[test]
+fn check_butter_bpf_01() {
+    fn check<const N: usize>() {
+        let (low, high) = (0.03, 0.11);
+        let bp = bpf::<N, Mo>(low, high);
+        assert_near(bp.gain_db(low), MINUS_3DB, 1e-9);
+        assert_near(bp.gain_db(high), MINUS_3DB, 1e-9);
+        let (wl, wh) = (warp(low), warp(high));
+        let center = (wl * wh).sqrt().atan() / Fp::PI;
+        assert_near(bp.gain_db(center), 0., 1e-9);
+        for freq in FREQS {
+            let w = warp(freq);
+            let ratio = (w * w - wl * wh) / (w * (wh - wl));
+            assert_near(bp.gain_db(freq), butter_gain_db(ratio, N), 1e-6);
+        }
+    }
+    check::<1>();
+    check::<2>();
+    check::<3>();
+    check::<4>();
+}
+
+#[test]
+fn check_butter_bpf_wide_01() {
+    // the real pole of odd orders makes two real poles
+    fn check<const N: usize>() {
+        let (low, high) = (0.01, 0.3);
+        let bp = bpf::<N, Mo>(low, high);
+        assert_near(bp.gain_db(low), MINUS_3DB, 1e-9);
+        assert_near(bp.gain_db(high), MINUS_3DB, 1e-9);
+        let (wl, wh) = (warp(low), warp(high));
+        for freq in [0.005, 0.05, 0.4] {
+            let w = warp(freq);
+            let ratio = (w * w - wl * wh) / (w * (wh - wl));
+            assert_near(bp.gain_db(freq), butter_gain_db(ratio, N), 1e-6);
+        }
+    }
+    check::<1>();
+    check::<2>();
+    check::<3>();
+}
+
+#[test]
+fn check_butter_proc_01() {
+    // settle to the gain at DC and Nyquist
+    let dc = vec![splat::<Mo>(1.); 4096];
+    let nyquist: Vec<Mo> = (0..4096)
+        .map(|n| splat(if n % 2 == 0 { 1. } else { -1. }))
+        .collect();
+
+    let frames = &mut dc.clone();
+    lpf8(0.1).proc(frames);
+    assert_near(frames[4095][0], 1., 1e-9);
+    let frames = &mut nyquist.clone();
+    hpf4(0.1).proc(frames);
+    assert_near(frames[4095][0], nyquist[4095][0], 1e-9);
+    let frames = &mut dc.clone();
+    bpf2(0.05, 0.2).proc(frames);
+    assert_near(frames[4095][0], 0., 1e-9);
+}
+
+#[test]
+fn check_butter_ctl_01() {
+    let mut hp = Hpf::<2, Mo>::new(0.1);
+    hp.control(|ctl| ctl.set_cutoff(0.2));
+    assert_near(hp.gain_db(0.2), MINUS_3DB, 1e-9);
+
+    let mut bp = Bpf::<3, Mo>::new(0.1, 0.2);
+    bp.control(|ctl| ctl.set_band(0.01, 0.02));
+    assert_near(bp.gain_db(0.01), MINUS_3DB, 1e-9);
+    assert_near(bp.gain_db(0.02), MINUS_3DB, 1e-9);
+}
@@ -0,0 +1,37 @@
+//! Helpers shared by the integration tests.
+
+#![allow(dead_code)]
+
+use sn_dsp::{
+    frame::{
+        splat,
+        Mo,
+    },
+    num::{
+        zero,
+        Fp,
+    },
+};
+
+// Relative precision to which results are checked, whatever the width of
+// `Fp`.
+const PRECISION: Fp = 1e3 * Fp::EPSILON;
+
+/// Assert that `x` is within `eps` of `y`.  Tolerances are written for
+/// `f64`: `eps` is raised to the precision of `Fp` relative to the larger
+/// of `x`, `y` and 1, so that the same checks hold with `f32`.
+pub fn assert_near(
+    x: Fp,
+    y: Fp,
+    eps: Fp,
+) {
+    let eps = eps.max(PRECISION * x.abs().max(y.abs()).max(1.));
+    assert!((x - y).abs() < eps, "{x} != {y}");
+}
+
+/// Unit impulse of `N` frames.
+pub fn impulse<const N: usize>() -> [Mo; N] {
+    let mut frames = [zero(); N];
+    frames[0] = splat(1.);
+    frames
+}
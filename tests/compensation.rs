@@ -0,0 +1,119 @@
+mod common;
+
+use common::impulse;
+use sn_dsp::{
+    feedback::Del,
+    frame::{
+        splat,
+        Mo,
+    },
+    graph::{
+        Graph,
+        Vertex,
+    },
+    mix::Mix,
+    node::{
+        Node,
+        StackNode,
+    },
+    num::zero,
+};
+
+#[test]
+fn check_compensation_mix_01() {
+    let mut mix = Mix::new(4);
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    mix.node_push(Del::alloc_new(3));
+    mix.set_compensation(true);
+    assert!(mix.compensation());
+    assert_eq!(mix.latency(), 3);
+
+    let frames = &mut impulse::<8>();
+    mix.proc(frames);
+
+    let mut expected = [zero(); 8];
+    expected[3] = splat(2.);
+    assert_eq!(frames, &expected);
+}
+
+#[test]
+fn check_compensation_mix_02() {
+    let mut mix = Mix::new(4);
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    mix.node_push(Del::alloc_new(3));
+    mix.set_compensation(true);
+    mix.set_compensation(false);
+
+    let frames = &mut impulse::<8>();
+    mix.proc(frames);
+
+    let mut expected = [zero(); 8];
+    expected[0] = splat(1.);
+    expected[3] = splat(1.);
+    assert_eq!(frames, &expected);
+}
+
+#[test]
+fn check_compensation_mix_prepare_01() {
+    let mut mix = Mix::new(4);
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    mix.set_compensation(true);
+    mix.node_push(Del::alloc_new(2));
+    mix.prepare(4, 48000.);
+
+    let frames = &mut impulse::<4>();
+    mix.proc(frames);
+
+    let mut expected = [zero(); 4];
+    expected[2] = splat(2.);
+    assert_eq!(frames, &expected);
+}
+
+#[test]
+fn check_compensation_graph_01() {
+    let mut graph = Graph::new(3);
+    let a = graph.node_add(Del::alloc_new(1));
+    let b = graph.node_add(Del::alloc_new(2));
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(a, b).unwrap();
+    graph.connect(b, Vertex::Output).unwrap();
+    graph.connect(Vertex::Input, b).unwrap();
+    graph.connect(Vertex::Input, Vertex::Output).unwrap();
+    graph.set_compensation(true);
+    assert_eq!(graph.latency(), 3);
+
+    let frames = &mut impulse::<8>();
+    graph.proc(frames);
+
+    let mut expected = [zero(); 8];
+    expected[3] = splat(3.);
+    assert_eq!(frames, &expected);
+}
+
+#[test]
+fn check_compensation_graph_02() {
+    let mut graph = Graph::new(4);
+    let a = graph.node_add(Del::alloc_new(2));
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(a, Vertex::Output).unwrap();
+    graph.connect(Vertex::Input, Vertex::Output).unwrap();
+
+    let frames = &mut impulse::<4>();
+    graph.proc(frames);
+    let mut expected = [zero(); 4];
+    expected[0] = splat(1.);
+    expected[2] = splat(1.);
+    assert_eq!(frames, &expected);
+
+    graph.set_compensation(true);
+    graph.prepare(4, 48000.);
+    let frames = &mut [zero(); 4];
+    graph.proc(frames);
+    assert_eq!(frames, &[zero(); 4]);
+
+    let frames = &mut impulse::<4>();
+    graph.proc(frames);
+    let mut expected = [zero(); 4];
+    expected[2] = splat(2.);
+    assert_eq!(frames, &expected);
+}
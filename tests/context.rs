@@ -0,0 +1,44 @@
+use sn_dsp::{
+    filter::{
+        Biquad,
+        BiquadCoefs,
+    },
+    frame::Mo,
+    node::Node,
+    num::zero,
+    oscillator::Sine,
+    Context,
+};
+
+#[test]
+fn check_context_units_01() {
+    let mut ctx = Context::new(48000., 256).with_tempo(90.);
+
+    assert_eq!(ctx.hz(12000.), 0.25);
+    assert_eq!(ctx.secs(0.5), 24000.);
+    assert_eq!(ctx.beats(3.), 96000.);
+
+    ctx.advance(256);
+    ctx.advance(256);
+    ctx.advance(23488);
+    assert_eq!(ctx.position, 24000);
+    assert_eq!(ctx.time(), 0.5);
+    assert_eq!(ctx.beat(), 0.75);
+}
+
+#[test]
+fn check_context_hz_01() {
+    let ctx = Context::new(8000., 64);
+
+    let mut sine = Sine::<Mo>::hz(2000., &ctx);
+    let frames = &mut [zero(); 4];
+    sine.proc(frames);
+    assert!((frames[1][0] - 1.).abs() < 1e-12);
+    assert!((frames[3][0] + 1.).abs() < 1e-12);
+
+    let a = BiquadCoefs::butter_lowpass_hz(1000., &ctx);
+    let b = BiquadCoefs::butter_lowpass(0.125);
+    assert_eq!(a.b0, b.b0);
+    assert_eq!(a.a1, b.a1);
+    let _ = Biquad::<Mo>::new(a);
+}
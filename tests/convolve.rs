@@ -0,0 +1,85 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    convolve::Convolver,
+    filter::Fir,
+    frame::{
+        splat,
+        Mo,
+        St,
+    },
+    node::Node,
+    num::Fp,
+};
+
+// Deterministic test signal.
+#[allow(clippy::cast_precision_loss)]
+fn signal(
+    len: usize,
+    seed: Fp,
+) -> Vec<Fp> {
+    (0..len)
+        .map(|n| ((n as Fp * seed).sin() * 1e3).fract())
+        .collect()
+}
+
+#[test]
+fn check_convolver_01() {
+    const BLOCK: usize = 64;
+    let ir = signal(1000, 0.7);
+    let ir_frames: Vec<Mo> = ir.iter().map(|x| splat(*x)).collect();
+    let mut conv = Convolver::new(&ir_frames, BLOCK);
+    assert_eq!(conv.latency(), BLOCK);
+    assert_eq!(conv.parts(), 16);
+    assert_eq!(conv.channels(), 1);
+
+    let input: Vec<Mo> = signal(3000, 0.3).iter().map(|x| splat(*x)).collect();
+    let mut expected = input.clone();
+    Fir::<Mo>::new(&ir).proc(&mut expected);
+
+    // blocks of arbitrary length
+    let mut output = input.clone();
+    for chunk in output.chunks_mut(37) {
+        conv.proc(chunk);
+    }
+    for frm in &output[..BLOCK] {
+        assert_eq!(frm[0], 0.);
+    }
+    for (x, y) in output[BLOCK..].iter().zip(&expected) {
+        assert_near(x[0], y[0], 1e-9);
+    }
+
+    conv.reset_state();
+    let mut again = input.clone();
+    conv.proc(&mut again);
+    assert_eq!(again, output);
+}
+
+#[test]
+fn check_convolver_channels_01() {
+    const BLOCK: usize = 16;
+    let left = signal(100, 0.9);
+    let right = signal(100, 0.2);
+    let ir: Vec<St> = left
+        .iter()
+        .zip(&right)
+        .map(|(l, r)| [*l, *r].into())
+        .collect();
+    let mut conv = Convolver::new(&ir, BLOCK);
+    assert_eq!(conv.channels(), 2);
+
+    let mut frames = vec![splat::<St>(0.); 200];
+    frames[0] = splat(1.);
+    conv.proc(&mut frames);
+    for n in 0..100 {
+        assert_near(frames[BLOCK + n][0], left[n], 1e-12);
+        assert_near(frames[BLOCK + n][1], right[n], 1e-12);
+    }
+}
+
+#[test]
+#[should_panic(expected = "power of two")]
+fn check_convolver_02() {
+    let _ = Convolver::<Mo>::new(&[splat(1.)], 48);
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        Crossover,
+        LrOrder,
+        Response,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::{
+        Complex,
+        Fp,
+        Real,
+        Zero,
+    },
+};
+
+const ORDERS: [LrOrder; 3] = [LrOrder::Lr2, LrOrder::Lr4, LrOrder::Lr8];
+const FREQS: [Fp; 7] = [0.001, 0.01, 0.03, 0.08, 0.15, 0.3, 0.45];
+
+fn sum_response(
+    xover: &Crossover<Mo>,
+    freq: Fp,
+) -> Complex {
+    xover
+        .as_slice()
+        .iter()
+        .fold(Complex::zero(), |acc, band| acc + band.response(freq))
+}
+
+#[test]
+fn check_crossover_two_bands_01() {
+    const MINUS_6DB: Fp = -6.020_599_913_279_624;
+    for order in ORDERS {
+        let xover = Crossover::<Mo>::new(order, &[0.05]);
+        assert_eq!(xover.len(), 2);
+        let [low, high] = xover.as_slice() else {
+            panic!()
+        };
+        assert_near(low.gain_db(0.05), MINUS_6DB, 1e-9);
+        assert_near(high.gain_db(0.05), MINUS_6DB, 1e-9);
+        // outputs are in phase at the crossover frequency
+        assert_near((low.response(0.05) / high.response(0.05)).arg(), 0., 1e-9);
+        assert_eq!(high.is_inverted(), order == LrOrder::Lr2);
+    }
+}
+
+#[test]
+fn check_crossover_allpass_01() {
+    for order in ORDERS {
+        let xover = Crossover::<Mo>::new(order, &[0.01, 0.04, 0.1, 0.2]);
+        assert_eq!(xover.len(), 5);
+        for freq in FREQS {
+            assert_near(sum_response(&xover, freq).norm(), 1., 1e-9);
+        }
+    }
+}
+
+#[test]
+fn check_crossover_proc_01() {
+    const LEN: usize = 1024;
+    let freqs = [0.02, 0.1];
+    let input: Vec<Mo> = (0..LEN)
+        .map(|n| splat((n as Fp * 0.37).sin() + (n as Fp * 0.05).cos()))
+        .collect();
+
+    let mut xover = Crossover::<Mo>::new(LrOrder::Lr4, &freqs);
+    let mut bands = vec![vec![splat(0.); LEN]; 3];
+    {
+        let mut outputs: Vec<&mut [Mo]> =
+            bands.iter_mut().map(Vec::as_mut_slice).collect();
+        xover.proc_bands(&input, &mut outputs);
+    }
+    let sum: Vec<Mo> =
+        (0..LEN).map(|n| bands.iter().map(|b| b[n]).sum()).collect();
+
+    let mut mix = Crossover::<Mo>::new(LrOrder::Lr4, &freqs).into_mix(64);
+    let mut mixed = input.clone();
+    mix.proc(&mut mixed);
+    for (x, y) in sum.iter().zip(&mixed) {
+        assert_near(x[0], y[0], 1e-12);
+    }
+
+    // the sum of an impulse has flat magnitude
+    let mut mix = Crossover::<Mo>::new(LrOrder::Lr8, &freqs).into_mix(64);
+    let mut ir = vec![splat::<Mo>(0.); 8192];
+    ir[0] = splat(1.);
+    mix.proc(&mut ir);
+    for freq in FREQS {
+        let h = ir.iter().enumerate().fold(Complex::zero(), |acc, (n, x)| {
+            acc + Complex::cis(-Fp::TAU * freq * n as Fp) * x[0]
+        });
+        assert_near(h.norm(), 1., 1e-6);
+    }
+}
@@ -0,0 +1,91 @@
+mod common;
+
+use common::impulse;
+use sn_dsp::{
+    feedback::{
+        Del,
+        Fbk,
+        Single,
+    },
+    filter::{
+        butter_lowpass,
+        lpf2,
+        BiquadCoefs,
+        DCBlock,
+        OnePole,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    oscillator::Sine,
+    smooth::Smooth,
+    util::Util,
+    Control,
+    Reset,
+};
+
+fn check_reset<N>(mut node: N)
+where
+    N: Node<Frame = Mo> + Reset,
+{
+    let first = &mut impulse::<8>();
+    node.proc(first);
+    node.reset();
+    let second = &mut impulse::<8>();
+    node.proc(second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn check_ctl_reset_filters_01() {
+    check_reset(butter_lowpass::<Mo>(0.1));
+    check_reset(lpf2::<Mo>(0.2));
+    check_reset(DCBlock::<Mo>::new(0.01));
+    check_reset(OnePole::<Mo>::new());
+}
+
+#[test]
+fn check_ctl_reset_delays_01() {
+    check_reset(Del::<Mo>::alloc_new(3));
+    check_reset(Single::<Mo>::new());
+    check_reset(Sine::<Mo>::new(0.1));
+    check_reset(Util::<Mo>::new());
+
+    let mut fbk = Fbk::<Mo>::new();
+    *fbk.feedback_mut() = 0.5;
+    fbk.bus_mut().node_push(Single::new());
+    let first = &mut impulse::<8>();
+    fbk.proc(first);
+    fbk.control(|ctl| ctl.reset());
+    let second = &mut impulse::<8>();
+    fbk.proc(second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn check_ctl_params_01() {
+    let mut sine = Sine::<Mo>::new(0.25);
+    sine.control(|ctl| ctl.freq.set(0.5));
+    assert_eq!(sine.freq.value(), 0.5);
+
+    let mut biquad = butter_lowpass::<Mo>(0.1);
+    biquad.control(|ctl| *ctl.coefs = BiquadCoefs::default());
+    let frames = &mut impulse::<2>();
+    biquad.proc(frames);
+    assert_eq!(frames, &impulse::<2>());
+
+    let mut util = Util::<Mo>::new();
+    util.control(|ctl| ctl.gain.set(2.));
+    assert_eq!(util.tick(splat(1.)), splat(2.));
+
+    let mut lpf = lpf2::<Mo>(0.1);
+    lpf.control(|ctl| ctl.set_cutoff(0.2));
+    let mut other = lpf2::<Mo>(0.2);
+    let frames = &mut impulse::<8>();
+    let expected = &mut impulse::<8>();
+    lpf.proc(frames);
+    other.proc(expected);
+    assert_eq!(frames, expected);
+}
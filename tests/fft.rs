@@ -0,0 +1,164 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    fft::{
+        Fft,
+        Istft,
+        RealFft,
+        Stft,
+        StftProc,
+    },
+    num::{
+        Complex,
+        Fp,
+        Real,
+        Zero,
+    },
+    window::Window,
+};
+
+// Deterministic test signal.
+#[allow(clippy::cast_precision_loss)]
+fn signal(
+    len: usize,
+    seed: Fp,
+) -> Vec<Fp> {
+    (0..len)
+        .map(|n| ((n as Fp * seed).sin() * 1e3).fract())
+        .collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn dft(x: &[Complex]) -> Vec<Complex> {
+    let n = x.len();
+    (0..n)
+        .map(|k| {
+            x.iter().enumerate().fold(Complex::zero(), |acc, (j, x)| {
+                acc + *x * Complex::cis(-Fp::TAU * (j * k) as Fp / n as Fp)
+            })
+        })
+        .collect()
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "f64"),
+    ignore = "checked against a direct DFT to f64 precision"
+)]
+fn check_fft_01() {
+    for size in [1, 2, 4, 8, 64, 256] {
+        let fft = Fft::new(size);
+        let re = signal(size, 0.3);
+        let im = signal(size, 0.7);
+        let input: Vec<Complex> = re
+            .iter()
+            .zip(&im)
+            .map(|(re, im)| Complex::new(*re, *im))
+            .collect();
+        let mut data = input.clone();
+        fft.forward(&mut data);
+        for (x, y) in data.iter().zip(dft(&input)) {
+            assert_near(x.re, y.re, 1e-9);
+            assert_near(x.im, y.im, 1e-9);
+        }
+        fft.inverse(&mut data);
+        for (x, y) in data.iter().zip(&input) {
+            assert_near(x.re, y.re * size as Fp, 1e-9);
+            assert_near(x.im, y.im * size as Fp, 1e-9);
+        }
+    }
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "f64"),
+    ignore = "checked against a direct DFT to f64 precision"
+)]
+fn check_real_fft_01() {
+    for size in [2, 4, 8, 32, 512] {
+        let fft = RealFft::new(size);
+        assert_eq!(fft.bins(), size / 2 + 1);
+        let input = signal(size, 0.11);
+        let mut spectrum = vec![Complex::zero(); fft.bins()];
+        fft.forward(&input, &mut spectrum);
+
+        let complex: Vec<Complex> =
+            input.iter().map(|x| Complex::from(*x)).collect();
+        for (x, y) in spectrum.iter().zip(dft(&complex)) {
+            assert_near(x.re, y.re, 1e-9);
+            assert_near(x.im, y.im, 1e-9);
+        }
+
+        let mut output = vec![0.; size];
+        fft.inverse(&mut spectrum, &mut output);
+        for (x, y) in output.iter().zip(&input) {
+            assert_near(*x, y * size as Fp, 1e-9);
+        }
+    }
+}
+
+#[test]
+fn check_stft_01() {
+    // a sine at the center of bin 8
+    const SIZE: usize = 64;
+    let mut stft = Stft::new(SIZE, 16, Window::Hann);
+    assert_eq!(stft.bins(), 33);
+    let input: Vec<Fp> = (0..256)
+        .map(|n| (Fp::TAU * 8. * n as Fp / SIZE as Fp).sin())
+        .collect();
+    let mut frames = 0;
+    stft.analyze(&input, |spectrum| {
+        frames += 1;
+        if frames >= 4 {
+            let peak = (0..spectrum.len())
+                .max_by(|a, b| {
+                    spectrum[*a].norm().total_cmp(&spectrum[*b].norm())
+                })
+                .unwrap();
+            assert_eq!(peak, 8);
+            // half the amplitude times the sum of the window
+            assert_near(spectrum[8].norm(), SIZE as Fp / 4., 1e-9);
+        }
+    });
+    assert_eq!(frames, 16);
+}
+
+#[test]
+fn check_stft_reconstruction_01() {
+    for (size, hop, window) in [
+        (64, 16, Window::Hann),
+        (64, 64, Window::Rectangular),
+        (128, 32, Window::Hann),
+    ] {
+        let mut proc = StftProc::new(size, hop, window);
+        assert_eq!(proc.latency(), size);
+        let input = signal(2048, 0.37);
+        let mut output = input.clone();
+        for chunk in output.chunks_mut(37) {
+            proc.proc(chunk, |_| {});
+        }
+        // steady state, after the frames overlapping the start
+        for (x, y) in output[2 * size..].iter().zip(&input[size..]) {
+            assert_near(*x, *y, 1e-9);
+        }
+    }
+}
+
+#[test]
+fn check_stft_proc_01() {
+    // gain applied to all bins
+    let mut proc = StftProc::new(32, 8, Window::Hann);
+    let mut samples = vec![0.5; 512];
+    proc.proc(&mut samples, |spectrum| {
+        for x in spectrum {
+            *x *= 2.;
+        }
+    });
+    assert_near(samples[511], 1., 1e-9);
+
+    let mut istft = Istft::new(8, 8, Window::Rectangular);
+    let mut spectrum = vec![Complex::zero(); 5];
+    spectrum[0] = Complex::from(8.);
+    assert_eq!(istft.synthesize(&mut spectrum), &[1.; 8]);
+}
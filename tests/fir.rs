@@ -0,0 +1,133 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        design_fir,
+        Fir,
+        FirKind,
+        Response,
+    },
+    frame::{
+        splat,
+        Mo,
+        St,
+    },
+    node::Node,
+    window::Window,
+};
+
+#[test]
+fn check_window_01() {
+    let mut w = [0.; 9];
+    Window::Hann.fill(&mut w);
+    assert_near(w[0], 0., 1e-12);
+    assert_near(w[4], 1., 1e-12);
+    assert_near(w[2], 0.5, 1e-12);
+    for window in [
+        Window::Rectangular,
+        Window::Hann,
+        Window::Hamming,
+        Window::Blackman,
+        Window::Kaiser(8.),
+    ] {
+        window.fill(&mut w);
+        for n in 0..9 {
+            assert_near(w[n], w[8 - n], 1e-12);
+        }
+        assert_near(w[4], 1., 1e-12);
+    }
+    Window::Kaiser(0.).fill(&mut w);
+    assert!(w.iter().all(|x| (x - 1.).abs() < 1e-12));
+    assert_near(Window::Hamming.value(0, 9), 0.08, 1e-12);
+    assert_near(Window::Blackman.value(0, 1), 1., 1e-12);
+}
+
+#[test]
+fn check_fir_design_01() {
+    const LEN: usize = 127;
+    let lp = Fir::<Mo>::design(FirKind::Lowpass(0.1), Window::Blackman, LEN);
+    assert_near(lp.gain_db(0.), 0., 1e-9);
+    assert_near(lp.gain_db(0.05), 0., 0.01);
+    assert!(lp.gain_db(0.15) < -60.);
+    assert!(lp.gain_db(0.45) < -60.);
+
+    let hp = Fir::<Mo>::design(FirKind::Hipass(0.1), Window::Blackman, LEN);
+    assert_near(hp.gain_db(0.5), 0., 1e-4);
+    assert_near(hp.gain_db(0.3), 0., 0.01);
+    assert!(hp.gain_db(0.) < -60.);
+
+    let bp =
+        Fir::<Mo>::design(FirKind::Bandpass(0.1, 0.2), Window::Kaiser(8.), LEN);
+    assert_near(bp.gain_db(0.15), 0., 0.01);
+    assert!(bp.gain_db(0.) < -60.);
+    assert!(bp.gain_db(0.4) < -60.);
+
+    let bs = Fir::<Mo>::design(FirKind::Bandstop(0.1, 0.2), Window::Hann, LEN);
+    assert!(bs.gain_db(0.15) < -40.);
+    assert_near(bs.gain_db(0.), 0., 0.01);
+    assert_near(bs.gain_db(0.5), 0., 0.01);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "f64"),
+    ignore = "group delay is checked to f64 precision"
+)]
+fn check_fir_linear_phase_01() {
+    const LEN: usize = 31;
+    let mut fir =
+        Fir::<St>::design(FirKind::Lowpass(0.2), Window::Hamming, LEN);
+    assert_eq!(fir.latency(), 15);
+    for freq in [0.01, 0.05, 0.1, 0.15] {
+        assert_near(fir.group_delay(freq), 15., 1e-6);
+    }
+
+    // the impulse response is the kernel
+    let mut frames = vec![splat::<St>(0.); 2 * LEN];
+    frames[0] = splat(1.);
+    fir.proc(&mut frames);
+    for (n, frm) in frames.iter().enumerate() {
+        let tap = fir.taps().get(n).copied().unwrap_or(0.);
+        assert_eq!(frm[0], tap);
+        assert_eq!(frm[1], tap);
+    }
+
+    let taps = [0.25, 0.5, 0.25];
+    let mut fir = Fir::<Mo>::linear_phase(&taps);
+    assert_eq!(fir.latency(), 1);
+    assert_eq!(Fir::<Mo>::new(&taps).latency(), 0);
+    let frames = &mut [splat::<Mo>(1.); 4];
+    fir.proc(frames);
+    assert_eq!(frames, &[splat(0.25), splat(0.75), splat(1.), splat(1.)]);
+    fir.reset_state();
+    let frames = &mut [splat::<Mo>(1.); 1];
+    fir.proc(frames);
+    assert_eq!(frames, &[splat(0.25)]);
+}
+
+#[test]
+#[should_panic(expected = "symmetric")]
+fn check_fir_linear_phase_02() {
+    let _ = Fir::<Mo>::linear_phase(&[1., 0.5]);
+}
+
+#[test]
+#[should_panic(expected = "odd")]
+fn check_fir_design_02() {
+    let mut taps = [0.; 32];
+    design_fir(FirKind::Hipass(0.1), Window::Hann, &mut taps);
+}
+
+#[test]
+#[should_panic(expected = "odd")]
+fn check_fir_design_03() {
+    // the center of an even kernel is half a frame off
+    let _ = Fir::<Mo>::design(FirKind::Lowpass(0.1), Window::Hann, 32);
+}
+
+#[test]
+#[should_panic(expected = "odd")]
+fn check_fir_linear_phase_03() {
+    let _ = Fir::<Mo>::linear_phase(&[0.5, 0.5]);
+}
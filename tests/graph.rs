@@ -0,0 +1,119 @@
+use sn_dsp::{
+    bus::Bus,
+    feedback::Del,
+    frame::{
+        splat,
+        Mo,
+    },
+    graph::{
+        Graph,
+        GraphError,
+        Vertex,
+    },
+    node::{
+        Node,
+        StackNode,
+    },
+    num::zero,
+};
+
+fn double() -> StackNode<Mo, impl FnMut(&mut [Mo])> {
+    StackNode::new(|frames: &mut [Mo]| {
+        for frm in frames {
+            *frm *= 2.;
+        }
+    })
+}
+
+#[test]
+fn check_graph_empty_01() {
+    let mut graph = Graph::new(4);
+
+    let frames = &mut [splat::<Mo>(1.); 3];
+    graph.proc(frames);
+
+    assert_eq!(frames, &[zero(); 3]);
+}
+
+#[test]
+fn check_graph_fan_01() {
+    let mut graph = Graph::new(2);
+    let a = graph.node_add(double());
+    let b = graph.node_add(Del::alloc_new(1));
+    let c = graph.node_add(double());
+
+    // input -> a -> (b, c) -> output, plus a dry send
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(a, b).unwrap();
+    graph.connect(a, c).unwrap();
+    graph.connect(b, Vertex::Output).unwrap();
+    graph.connect(c, Vertex::Output).unwrap();
+    graph
+        .connect_gain(Vertex::Input, Vertex::Output, 0.5)
+        .unwrap();
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    let frames = &mut [imp, sil, sil, imp, sil];
+    let expected = &[imp * 4.5, imp * 2., sil, imp * 4.5, imp * 2.];
+    graph.proc(frames);
+
+    assert_eq!(frames, expected);
+}
+
+#[test]
+fn check_graph_order_01() {
+    let mut graph = Graph::new(8);
+    // added in reverse order of processing
+    let b = graph.node_add(double());
+    let a = graph.node_add(Del::alloc_new(1));
+
+    graph.connect(a, b).unwrap();
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(b, Vertex::Output).unwrap();
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    let frames = &mut [imp, sil, sil];
+    graph.proc(frames);
+
+    assert_eq!(frames, &[sil, imp * 2., sil]);
+}
+
+#[test]
+fn check_graph_cycle_01() {
+    let mut graph = Graph::<Mo>::new(8);
+    let a = graph.node_add(double());
+    let b = graph.node_add(double());
+
+    graph.connect(a, b).unwrap();
+    assert_eq!(graph.connect(b, a), Err(GraphError::Cycle));
+    assert_eq!(graph.connect(a, a), Err(GraphError::Cycle));
+    assert_eq!(
+        graph.connect(Vertex::Output, a),
+        Err(GraphError::InvalidVertex(Vertex::Output))
+    );
+    assert_eq!(
+        graph.connect(a, Vertex::Node(2)),
+        Err(GraphError::InvalidVertex(Vertex::Node(2)))
+    );
+}
+
+#[test]
+fn check_graph_nested_01() {
+    let mut graph = Graph::new(8);
+    let a = graph.node_add(double());
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(a, Vertex::Output).unwrap();
+
+    let mut bus = Bus::new();
+    bus.node_push(graph);
+    bus.node_push(double());
+
+    let frames = &mut [splat::<Mo>(1.); 3];
+    bus.proc(frames);
+
+    assert_eq!(frames, &[splat(4.); 3]);
+}
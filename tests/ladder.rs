@@ -0,0 +1,105 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        ladder,
+        Ladder,
+        LadderSlope,
+    },
+    frame::{
+        splat,
+        Mo,
+        St,
+    },
+    node::Node,
+    num::{
+        Fp,
+        Real,
+    },
+    Control,
+    Reset,
+};
+
+// Peak amplitude of the steady-state response to a small sine.
+fn amplitude(
+    filter: &mut Ladder<Mo>,
+    freq: Fp,
+) -> Fp {
+    let mut frames: Vec<Mo> = (0..8192)
+        .map(|n| splat(1e-3 * (Fp::TAU * freq * n as Fp).sin()))
+        .collect();
+    filter.proc(&mut frames);
+    frames[4096..].iter().map(|f| f[0].abs()).fold(0., Fp::max) / 1e-3
+}
+
+#[test]
+fn check_ladder_slope_01() {
+    let mut lp = ladder::<Mo>(0.01, 0.);
+    let mut frames = vec![splat(1e-3); 4096];
+    lp.proc(&mut frames);
+    assert_near(frames[4095][0], 1e-3, 1e-8);
+
+    // an octave is 6 dB per pole
+    let mut lp24 = ladder::<Mo>(0.01, 0.);
+    let mut lp12 = ladder::<Mo>(0.01, 0.);
+    lp12.slope = LadderSlope::Db12;
+    let a24 = amplitude(&mut lp24, 0.08) / amplitude(&mut lp24, 0.16);
+    let a12 = amplitude(&mut lp12, 0.08) / amplitude(&mut lp12, 0.16);
+    assert!(20. * a24.log10() > 20., "{a24}");
+    assert!(20. * a12.log10() > 10. && 20. * a12.log10() < 20., "{a12}");
+}
+
+#[test]
+fn check_ladder_resonance_01() {
+    // decays below the edge of self-oscillation
+    let mut filter = ladder::<Mo>(0.05, 0.8);
+    let mut frames = vec![splat(0.); 48_000];
+    frames[0] = splat(1.);
+    filter.proc(&mut frames);
+    assert!(frames[40_000..].iter().all(|f| f[0].abs() < 1e-6));
+
+    // oscillates on its own above it, and stays bounded
+    let mut filter = ladder::<Mo>(0.05, 1.2);
+    let mut frames = vec![splat(0.); 48_000];
+    frames[0] = splat(1e-3);
+    filter.proc(&mut frames);
+    let tail = frames[40_000..]
+        .iter()
+        .map(|f| f[0].abs())
+        .fold(0., Fp::max);
+    assert!(tail > 0.1 && tail < 2., "{tail}");
+
+    // hard drive with resonance stays bounded
+    let mut filter = Ladder::<St>::new(0.2, 1.);
+    filter.drive = 100.;
+    let mut frames: Vec<St> =
+        (0..4096).map(|n| splat((n as Fp * 0.1).sin())).collect();
+    filter.proc(&mut frames);
+    assert!(frames.iter().all(|f| f[0].is_finite() && f[0].abs() < 2.));
+    assert_eq!(frames[4095][0], frames[4095][1]);
+}
+
+#[test]
+fn check_ladder_ctl_01() {
+    let mut filter = ladder::<Mo>(0.05, 0.5);
+    let mut frames = vec![splat(1.); 16];
+    filter.proc(&mut frames);
+    filter.control(|ctl| {
+        ctl.set_cutoff(0.1);
+        *ctl.resonance = 0.2;
+        *ctl.slope = LadderSlope::Db12;
+    });
+    assert_near(filter.cutoff(), 0.1, 1e-12);
+    assert_near(filter.resonance, 0.2, 1e-12);
+    assert_eq!(filter.slope, LadderSlope::Db12);
+
+    let mut fresh = ladder::<Mo>(0.1, 0.2);
+    fresh.slope = LadderSlope::Db12;
+    filter.control(|ctl| ctl.reset());
+    let mut a = vec![splat(1.); 16];
+    let mut b = a.clone();
+    filter.proc(&mut a);
+    fresh.proc(&mut b);
+    assert_eq!(a, b);
+}
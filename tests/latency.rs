@@ -0,0 +1,129 @@
+use sn_dsp::{
+    bus::Bus,
+    feedback::{
+        Del,
+        Fbk,
+        Single,
+    },
+    frame::Mo,
+    graph::{
+        Graph,
+        Vertex,
+    },
+    mix::Mix,
+    node::{
+        Node,
+        StackNode,
+    },
+    num::Fp,
+    Context,
+};
+
+#[derive(Debug, Default)]
+struct Prep {
+    max_block:   usize,
+    sample_rate: Fp,
+}
+
+impl Node for Prep {
+    type Frame = Mo;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        assert!(frames.len() <= self.max_block);
+    }
+
+    fn prepare(
+        &mut self,
+        max_block: usize,
+        sample_rate: Fp,
+    ) {
+        self.max_block = max_block;
+        self.sample_rate = sample_rate;
+    }
+}
+
+#[test]
+fn check_latency_bus_01() {
+    let mut bus = Bus::<Mo>::new();
+    assert_eq!(bus.latency(), 0);
+
+    bus.node_push(Del::alloc_new(3));
+    bus.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    bus.node_push(Del::alloc_new(5).then(Del::alloc_new(1)));
+
+    assert_eq!(bus.latency(), 9);
+
+    let mut fbk = Fbk::new();
+    fbk.bus_mut().node_push(bus);
+    assert_eq!(fbk.latency(), 9);
+}
+
+#[test]
+fn check_latency_single_01() {
+    let mut single = Single::<Mo>::new();
+    assert_eq!(single.latency(), 1);
+
+    let frames = &mut [Mo::from([1.]), Mo::from([0.])];
+    single.proc(frames);
+    assert_eq!(frames, &[Mo::from([0.]), Mo::from([1.])]);
+
+    let mut mix = Mix::<Mo>::new(4);
+    mix.node_push(Single::new());
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    assert_eq!(mix.latency(), 1);
+}
+
+#[test]
+fn check_latency_mix_01() {
+    let mut mix = Mix::<Mo>::new(8);
+    mix.node_push(Del::alloc_new(3));
+    mix.node_push(Del::alloc_new(5).bypass());
+
+    assert_eq!(mix.latency(), 5);
+}
+
+#[test]
+fn check_latency_graph_01() {
+    let mut graph = Graph::<Mo>::new(8);
+    let a = graph.node_add(Del::alloc_new(2));
+    let b = graph.node_add(Del::alloc_new(3));
+    let c = graph.node_add(Del::alloc_new(7));
+
+    graph.connect(Vertex::Input, a).unwrap();
+    graph.connect(a, b).unwrap();
+    graph.connect(b, Vertex::Output).unwrap();
+    graph.connect(Vertex::Input, Vertex::Output).unwrap();
+    assert_eq!(graph.latency(), 5);
+
+    // not connected to the output
+    graph.connect(a, c).unwrap();
+    assert_eq!(graph.latency(), 5);
+
+    graph.connect(c, Vertex::Output).unwrap();
+    assert_eq!(graph.latency(), 9);
+
+    graph.replace(2, Box::new(Del::alloc_new(1)));
+    assert_eq!(graph.latency(), 5);
+    graph.disconnect(a, b);
+    assert_eq!(graph.latency(), 3);
+}
+
+#[test]
+fn check_prepare_01() {
+    let ctx = Context::new(44100., 16);
+
+    let mut node = Prep::default().then(Prep::default()).bypass();
+    ctx.prepare(&mut node);
+
+    let inner = node.node();
+    assert_eq!(inner.first().max_block, 16);
+    assert_eq!(inner.second().sample_rate, 44100.);
+
+    let mut bus = Bus::new();
+    bus.node_push(node);
+    ctx.prepare(&mut bus);
+    bus.proc(&mut [Mo::from([0.]); 16]);
+}
@@ -0,0 +1,62 @@
+use sn_dsp::{
+    feedback::Del,
+    frame::{
+        splat,
+        Mo,
+    },
+    mix::Mix,
+    node::{
+        Node,
+        StackNode,
+    },
+    num::zero,
+};
+
+#[test]
+fn check_mix_empty_01() {
+    let mut mix = Mix::new(4);
+
+    let frames = &mut [splat::<Mo>(1.); 3];
+    mix.proc(frames);
+
+    assert_eq!(frames, &[zero(); 3]);
+}
+
+#[test]
+fn check_mix_del_01() {
+    let mut mix = Mix::new(2);
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    mix.node_push(Del::alloc_new(2));
+    *mix.gain_mut(1) = 0.5;
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    // chunks longer than the block size are split
+    let frames = &mut [imp, sil, sil, imp, sil];
+    let expected = &[imp, sil, imp * 0.5, imp, sil];
+    mix.proc(frames);
+
+    assert_eq!(frames, expected);
+}
+
+#[test]
+fn check_mix_remove_01() {
+    let mut mix = Mix::new(8);
+    mix.node_push(StackNode::new(|frames: &mut [Mo]| {
+        for frm in frames {
+            *frm *= 2.;
+        }
+    }));
+    mix.node_push(StackNode::new(|_: &mut [Mo]| {}));
+    *mix.gain_mut(1) = 0.25;
+
+    let _ = mix.remove(0);
+    assert_eq!(mix.len(), 1);
+    assert_eq!(*mix.gain(0), 0.25);
+
+    let frames = &mut [splat(4.); 2];
+    mix.proc(frames);
+
+    assert_eq!(frames, &[splat(1.); 2]);
+}
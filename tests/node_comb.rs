@@ -0,0 +1,88 @@
+use sn_dsp::{
+    bus::Bus,
+    feedback::Del,
+    frame::{
+        splat,
+        Mo,
+    },
+    node::{
+        Node,
+        StackNode,
+    },
+    num::{
+        zero,
+        Fp,
+    },
+};
+
+fn gain(g: Fp) -> StackNode<Mo, impl FnMut(&mut [Mo])> {
+    StackNode::new(move |frames: &mut [Mo]| {
+        for frm in frames {
+            *frm *= g;
+        }
+    })
+}
+
+#[test]
+fn check_then_01() {
+    let mut node = Del::<Mo>::alloc_new(1).then(gain(3.));
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    let frames = &mut [imp, sil, sil];
+    node.proc(frames);
+
+    assert_eq!(frames, &[sil, imp * 3., sil]);
+}
+
+#[test]
+fn check_mix_01() {
+    let mut node = Del::<Mo>::alloc_new(1).mix(gain(3.)).then(gain(2.));
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    // longer than the internal scratch buffer
+    let frames = &mut [sil; 100];
+    frames[70] = imp;
+    node.proc(frames);
+
+    let expected = &mut [sil; 100];
+    expected[70] = imp * 6.;
+    expected[71] = imp * 2.;
+    assert_eq!(frames, expected);
+}
+
+#[test]
+fn check_split_01() {
+    let mut node = gain(3.).split();
+    node.dry = 0.5;
+
+    let frames = &mut [splat::<Mo>(1.); 2];
+    node.proc(frames);
+
+    assert_eq!(frames, &[splat(3.5); 2]);
+}
+
+#[test]
+fn check_bypass_01() {
+    let mut node = gain(3.).bypass();
+
+    assert_eq!(node.tick(splat(1.)), splat(3.));
+    node.toggle();
+    assert_eq!(node.tick(splat(1.)), splat(1.));
+    node.set_bypassed(false);
+    assert_eq!(node.tick(splat(1.)), splat(3.));
+}
+
+#[test]
+fn check_comb_in_bus_01() {
+    let mut bus = Bus::new();
+    bus.node_push(gain(2.).then(gain(2.)).mix(gain(1.)));
+
+    let frames = &mut [splat::<Mo>(1.); 2];
+    bus.proc(frames);
+
+    assert_eq!(frames, &[splat(5.); 2]);
+}
@@ -0,0 +1,109 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    bus::Bus,
+    feedback::Del,
+    frame::{
+        splat,
+        Mo,
+        Qd,
+        St,
+    },
+    node::Node,
+    num::{
+        zero,
+        Fp,
+    },
+    processor::{
+        NodeProcessor,
+        Pan,
+        Processor,
+        ProcessorNode,
+        Remix,
+    },
+};
+
+#[test]
+fn check_remix_01() {
+    let mut up = Remix::<Mo, St>::new();
+    let mut down = Remix::<Qd, St>::new();
+    let mut mono = Remix::<Qd, Mo>::new();
+
+    let input = [Mo::from([1.])];
+    let output = &mut [zero(); 1];
+    up.process(&input, &[], output);
+    assert_eq!(output, &[St::from([1., 1.])]);
+
+    let input = [Qd::from([1., 2., 3., 4.])];
+    let output = &mut [zero(); 1];
+    down.process(&input, &[], output);
+    assert_eq!(output, &[St::from([2., 3.])]);
+
+    let output = &mut [zero(); 1];
+    mono.process(&input, &[], output);
+    assert_eq!(output, &[Mo::from([2.5])]);
+}
+
+#[test]
+fn check_pan_01() {
+    let mut pan = Pan::<Fp>::new(-1.);
+
+    let input = [splat(1.); 2];
+    let output = &mut [zero(); 2];
+    pan.process(&input, &[], output);
+    assert_near(output[0][0], 1., 1e-12);
+    assert_near(output[1][1], 0., 1e-12);
+
+    pan.pan = 0.;
+    pan.process(&input, &[], output);
+    assert_near(output[0][0], output[0][1], 1e-12);
+    assert_near(output[0][0].powi(2) + output[0][1].powi(2), 1., 1e-12);
+}
+
+#[test]
+fn check_node_processor_01() {
+    let mut proc = NodeProcessor::new(Del::<Mo>::alloc_new(1));
+
+    let sil = zero();
+    let imp = splat(1.);
+
+    let input = [imp, sil, sil];
+    let output = &mut [sil; 3];
+    proc.process(&input, &[], output);
+    assert_eq!(output, &[sil, imp, sil]);
+    assert_eq!(input, [imp, sil, sil]);
+
+    // and back
+    let mut bus = Bus::new();
+    bus.node_push(ProcessorNode::new(proc));
+
+    let frames = &mut [imp, sil, sil];
+    bus.proc(frames);
+    assert_eq!(frames, &[sil, imp, sil]);
+}
+
+#[test]
+fn check_node_processor_02() {
+    let mut proc = NodeProcessor::new(Del::<Mo>::alloc_new(3));
+    assert_eq!(proc.latency(), 3);
+
+    proc.process(&[splat(1.)], &[], &mut [zero()]);
+    proc.reset_state();
+    let output = &mut [splat(1.); 3];
+    proc.process(&[zero(); 3], &[], output);
+    assert_eq!(output, &[zero(); 3]);
+
+    // hooks are forwarded both ways
+    let node = ProcessorNode::new(proc);
+    assert_eq!(node.latency(), 3);
+    let mut bus = Bus::new();
+    bus.node_push(node);
+    assert_eq!(bus.latency(), 3);
+
+    bus.proc(&mut [splat(1.)]);
+    bus.reset_state();
+    let frames = &mut [zero(); 3];
+    bus.proc(frames);
+    assert_eq!(frames, &[zero(); 3]);
+}
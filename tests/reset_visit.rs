@@ -0,0 +1,148 @@
+mod common;
+
+use common::impulse;
+use sn_dsp::{
+    bus::Bus,
+    feedback::{
+        Del,
+        Fbk,
+    },
+    filter::lpf2,
+    frame::{
+        splat,
+        Mo,
+    },
+    mix::Mix,
+    node::Node,
+    num::Fp,
+    oscillator::Sine,
+    util::Util,
+    Reset,
+    Visit,
+};
+
+#[derive(Debug, Default)]
+struct Names {
+    path:  Vec<usize>,
+    names: Vec<String>,
+}
+
+impl Visit for Names {
+    fn param(
+        &mut self,
+        name: &str,
+        value: &mut Fp,
+    ) {
+        let path: Vec<_> = self.path.iter().map(ToString::to_string).collect();
+        self.names
+            .push(format!("{}/{name}={value}", path.join("/")));
+    }
+
+    fn enter(
+        &mut self,
+        index: usize,
+    ) {
+        self.path.push(index);
+    }
+
+    fn leave(&mut self) {
+        self.path.pop();
+    }
+}
+
+fn patch() -> Fbk<'static, Mo> {
+    let mut fbk = Fbk::new();
+    *fbk.feedback_mut() = 0.5;
+    fbk.bus_mut()
+        .node_push(Del::alloc_new(2))
+        .node_push(lpf2(0.1))
+        .node_push(Sine::new(0.25).mix(Util::new()));
+    fbk
+}
+
+#[test]
+fn check_reset_state_bus_01() {
+    let mut fbk = patch();
+    let first = &mut impulse::<16>();
+    fbk.proc(first);
+    fbk.reset_state();
+    let second = &mut impulse::<16>();
+    fbk.proc(second);
+    assert_eq!(first, second);
+
+    let mut bus = Bus::new();
+    bus.node_push(patch());
+    let first = &mut impulse::<16>();
+    bus.proc(first);
+    bus.reset();
+    let second = &mut impulse::<16>();
+    bus.proc(second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn check_reset_fbk_ctl_01() {
+    let mut fbk = patch();
+    let first = &mut impulse::<16>();
+    fbk.proc(first);
+    fbk.reset();
+    let second = &mut impulse::<16>();
+    fbk.proc(second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn check_reset_state_mix_01() {
+    let mut mix = Mix::new(4);
+    mix.node_push(patch()).node_push(Del::alloc_new(5));
+    mix.set_compensation(true);
+    let first = &mut impulse::<16>();
+    mix.proc(first);
+    mix.reset_state();
+    let second = &mut impulse::<16>();
+    mix.proc(second);
+    assert_eq!(first, second);
+}
+
+#[test]
+fn check_visit_params_01() {
+    let mut fbk = patch();
+    let mut names = Names::default();
+    fbk.visit_params(&mut names);
+
+    let has = |name: &str| names.names.iter().any(|n| n == name);
+    let has_prefix =
+        |prefix: &str| names.names.iter().any(|n| n.starts_with(prefix));
+    assert!(has("/feedback=0.5"));
+    // the delay has no parameters
+    assert!(!has_prefix("0/"));
+    // each section of the filter is entered
+    assert!(has_prefix("1/0/a1="));
+    assert!(has_prefix("1/1/a1="));
+    assert!(!has_prefix("1/2/"));
+    assert!(has("2/0/freq=0.25"));
+    assert!(has("2/1/gain=1"));
+    // every enter is matched by a leave
+    assert!(names.path.is_empty());
+}
+
+#[test]
+fn check_visit_params_set_01() {
+    struct Half;
+
+    impl Visit for Half {
+        fn param(
+            &mut self,
+            _name: &str,
+            value: &mut Fp,
+        ) {
+            *value *= 0.5;
+        }
+    }
+
+    let mut mix = Mix::new(4);
+    mix.node_push(Util::<Mo>::new());
+    mix.visit_params(&mut Half);
+    assert_eq!(*mix.gain(0), 0.5);
+    assert_eq!(mix.tick(splat(1.)), splat(0.25));
+}
@@ -0,0 +1,99 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        butter_hipass,
+        butter_lowpass,
+        lpf2,
+        Biquad,
+        BiquadCoefs,
+        DCBlock,
+        Response,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::{
+        zero,
+        Complex,
+        Fp,
+        Real,
+    },
+};
+
+const MINUS_3DB: Fp = -3.010_299_956_639_812;
+
+#[test]
+fn check_response_butter_01() {
+    let lp = BiquadCoefs::butter_lowpass(0.1);
+    assert_near(lp.gain_db(0.1), MINUS_3DB, 1e-9);
+    assert_near(lp.gain_db(0.), 0., 1e-9);
+    assert!(lp.gain_db(0.4) < -36.);
+    assert_near(lp.phase(0.1), -Fp::PI / 2., 1e-9);
+
+    let hp = butter_hipass::<Mo>(0.05);
+    assert_near(hp.gain_db(0.05), MINUS_3DB, 1e-9);
+    assert_near(hp.gain_db(0.5), 0., 1e-9);
+    assert!(hp.gain_db(0.001) < -36.);
+}
+
+#[test]
+fn check_response_cascade_01() {
+    let lpf = lpf2::<Mo>(0.2);
+    assert_near(lpf.gain_db(0.2), MINUS_3DB, 1e-9);
+
+    let chain = butter_lowpass::<Mo>(0.1).then(butter_hipass(0.01));
+    let expected = BiquadCoefs::butter_lowpass(0.1).gain_db(0.05)
+        + BiquadCoefs::butter_hipass(0.01).gain_db(0.05);
+    assert_near(chain.gain_db(0.05), expected, 1e-9);
+
+    let mut split = Biquad::<Mo>::new(BiquadCoefs::butter_lowpass(0.1)).split();
+    split.dry = 1.;
+    split.wet = -1.;
+    assert_near(split.response(0.).norm(), 0., 1e-9);
+
+    let mut bypass = DCBlock::<Mo>::new(0.01).bypass();
+    assert!(bypass.gain_db(0.) < -100.);
+    bypass.toggle();
+    assert_eq!(bypass.response(0.), Complex::from(1.));
+}
+
+#[test]
+#[allow(clippy::cast_precision_loss)]
+fn check_response_impulse_01() {
+    // response agrees with the DFT of the impulse response
+    let coefs = BiquadCoefs::resonator(0.1, 0.05);
+    let mut biquad = Biquad::<Mo>::new(coefs);
+    let frames = &mut [zero(); 2048];
+    frames[0] = splat(1.);
+    biquad.proc(frames);
+
+    let freq = 0.125;
+    let dft = frames
+        .iter()
+        .enumerate()
+        .fold(Complex::default(), |acc, (n, x)| {
+            acc + Complex::cis(-Fp::TAU * freq * n as Fp) * x[0]
+        });
+    let resp = coefs.response(freq);
+    assert_near(dft.re, resp.re, 1e-9);
+    assert_near(dft.im, resp.im, 1e-9);
+}
+
+#[test]
+#[cfg_attr(
+    not(feature = "f64"),
+    ignore = "group delay is checked to f64 precision"
+)]
+fn check_response_group_delay_01() {
+    // a symmetric FIR filter has constant group delay
+    let fir = BiquadCoefs::arbitrary(0., 0., 0.25, 0.5, 0.25);
+    assert_near(fir.group_delay(0.1), 1., 1e-6);
+    assert_near(fir.group_delay(0.3), 1., 1e-6);
+
+    let del = BiquadCoefs::arbitrary(0., 0., 0., 0., 1.);
+    assert_near(del.group_delay(0.2), 2., 1e-6);
+}
@@ -0,0 +1,155 @@
+use sn_dsp::{
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::Fp,
+    sched::{
+        Driver,
+        Scheduler,
+    },
+    Control,
+};
+
+#[derive(Debug)]
+struct Gain {
+    gain:  Fp,
+    calls: usize,
+}
+
+struct GainCtl<'a> {
+    gain: &'a mut Fp,
+}
+
+impl Node for Gain {
+    type Frame = Mo;
+
+    fn proc(
+        &mut self,
+        frames: &mut [Self::Frame],
+    ) {
+        self.calls += 1;
+        for frm in frames {
+            *frm *= self.gain;
+        }
+    }
+}
+
+impl Control for Gain {
+    type Ctl<'a> = GainCtl<'a>;
+
+    fn control(
+        &mut self,
+        f: impl FnOnce(&mut Self::Ctl<'_>),
+    ) {
+        f(&mut GainCtl {
+            gain: &mut self.gain,
+        });
+    }
+}
+
+fn gain() -> Gain {
+    Gain {
+        gain:  1.,
+        calls: 0,
+    }
+}
+
+#[test]
+fn check_sched_01() {
+    let mut sched = Scheduler::new(gain(), 4, |ctl: &mut GainCtl, g| {
+        *ctl.gain = g;
+    });
+    sched.schedule(2, 2.).unwrap();
+    sched.schedule(5, 3.).unwrap();
+    assert_eq!(sched.len(), 2);
+    assert_eq!(sched.next_time(), Some(2));
+
+    let frames = &mut [splat::<Mo>(1.); 4];
+    sched.proc(frames);
+    assert_eq!(frames, &[splat(1.), splat(1.), splat(2.), splat(2.)]);
+    assert_eq!(sched.position(), 4);
+    assert_eq!(sched.len(), 1);
+
+    let frames = &mut [splat::<Mo>(1.); 4];
+    sched.proc(frames);
+    assert_eq!(frames, &[splat(2.), splat(3.), splat(3.), splat(3.)]);
+    assert!(sched.is_empty());
+    assert_eq!(sched.node().calls, 4);
+}
+
+#[test]
+fn check_sched_order_01() {
+    let mut sched = Scheduler::new(gain(), 3, |ctl: &mut GainCtl, g| {
+        *ctl.gain = g;
+    });
+    sched.schedule(1, 5.).unwrap();
+    sched.schedule(1, 4.).unwrap();
+    sched.schedule(0, 2.).unwrap();
+    assert_eq!(sched.schedule(0, 7.), Err(7.));
+
+    let frames = &mut [splat::<Mo>(1.); 2];
+    sched.proc(frames);
+    assert_eq!(frames, &[splat(2.), splat(4.)]);
+    assert_eq!(sched.node().calls, 2);
+}
+
+#[test]
+fn check_sched_past_01() {
+    let mut sched = Scheduler::new(gain(), 2, |ctl: &mut GainCtl, g| {
+        *ctl.gain = g;
+    });
+    sched.set_position(10);
+    sched.schedule(3, 2.).unwrap();
+
+    let frames = &mut [splat::<Mo>(1.); 2];
+    sched.proc(frames);
+    assert_eq!(frames, &[splat(2.); 2]);
+
+    sched.schedule(100, 3.).unwrap();
+    sched.flush();
+    assert!(sched.is_empty());
+    assert_eq!(sched.node().gain, 3.);
+}
+
+#[test]
+fn check_driver_01() {
+    let mut count = 0_u8;
+    let mut driver = Driver::new(gain(), |ctl: &mut GainCtl| {
+        count += 1;
+        *ctl.gain = Fp::from(count);
+        3
+    });
+
+    let frames = &mut [splat::<Mo>(1.); 4];
+    driver.proc(frames);
+    assert_eq!(frames, &[splat(1.), splat(1.), splat(1.), splat(2.)]);
+    assert_eq!(driver.remain(), 2);
+
+    let frames = &mut [splat::<Mo>(1.); 4];
+    driver.proc(frames);
+    assert_eq!(frames, &[splat(2.), splat(2.), splat(3.), splat(3.)]);
+    assert_eq!(driver.node().calls, 4);
+}
+
+#[test]
+fn check_driver_period_01() {
+    let mut periods = [0, 2, 1].into_iter().cycle();
+    let mut driver = Driver::new(gain(), |ctl: &mut GainCtl| {
+        *ctl.gain += 1.;
+        periods.next().unwrap()
+    });
+
+    let frames = &mut [splat::<Mo>(1.); 5];
+    driver.proc(frames);
+    assert_eq!(
+        frames,
+        &[splat(2.), splat(3.), splat(3.), splat(4.), splat(5.)]
+    );
+
+    driver.restart();
+    let frames = &mut [splat::<Mo>(1.); 1];
+    driver.proc(frames);
+    assert_eq!(frames, &[splat(6.)]);
+}
@@ -0,0 +1,110 @@
+use sn_dsp::{
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::Fp,
+    oscillator::Sine,
+    smooth::{
+        Expo,
+        Glide,
+        Slew,
+        Smooth,
+    },
+    util::Util,
+    Control,
+    Reset,
+};
+
+#[test]
+fn check_smooth_slew_01() {
+    let mut slew = Slew::new(0., 0.25);
+    slew.set(-0.6);
+    assert_eq!(slew.tick(), -0.25);
+    assert_eq!(slew.tick(), -0.5);
+    assert!(!slew.is_settled());
+    assert_eq!(slew.tick(), -0.6);
+    assert!(slew.is_settled());
+}
+
+#[test]
+fn check_smooth_expo_01() {
+    let mut expo = Expo::new(1., 10.);
+    expo.set(0.);
+    let first = expo.tick();
+    assert!((first - (-0.1 as Fp).exp()).abs() < 1e-12);
+    for _ in 0..1000 {
+        expo.tick();
+    }
+    assert!(expo.is_settled());
+    assert_eq!(expo.value(), 0.);
+}
+
+#[test]
+fn check_smooth_glide_01() {
+    let mut glide = Glide::new(0., 4);
+    glide.set(1.);
+    let values: Vec<_> = (0..5).map(|_| glide.tick()).collect();
+    assert_eq!(values, [0.25, 0.5, 0.75, 1., 1.]);
+    assert!(glide.is_settled());
+
+    glide.set_time(0);
+    glide.set(3.);
+    assert_eq!(glide.value(), 3.);
+
+    glide.set_time(8);
+    glide.set(5.);
+    glide.tick();
+    glide.finish();
+    assert_eq!(glide.value(), 5.);
+}
+
+#[test]
+fn check_smooth_util_01() {
+    let mut util = Util::<Mo>::new();
+    util.control(|ctl| {
+        ctl.gain.set_time(2);
+        ctl.gain.set(0.);
+    });
+    let frames = &mut [splat::<Mo>(1.); 3];
+    util.proc(frames);
+    assert_eq!(frames, &[splat(0.5), splat(0.), splat(0.)]);
+}
+
+#[test]
+fn check_smooth_sine_01() {
+    let mut sine = Sine::<Mo>::new(0.);
+    sine.freq.set_time(4);
+    sine.freq.set(0.1);
+    let frames = &mut [splat::<Mo>(0.); 5];
+    sine.proc(frames);
+    assert!((sine.phase - 0.35).abs() < 1e-12);
+    assert!(sine.freq.is_settled());
+}
+
+#[test]
+fn check_smooth_reset_01() {
+    // parameters are kept: glides go on
+    let mut sine = Sine::<Mo>::new(0.);
+    sine.freq.set_time(4);
+    sine.freq.set(0.1);
+    sine.proc(&mut [splat(0.)]);
+    sine.reset_state();
+    assert_eq!(sine.phase, 0.);
+    assert_eq!(sine.freq.value(), 0.025);
+    sine.control(|ctl| ctl.reset());
+    assert_eq!(sine.freq.value(), 0.025);
+    assert!(!sine.freq.is_settled());
+
+    let mut util = Util::<Mo>::new();
+    util.gain.set_time(2);
+    util.gain.set(0.);
+    util.proc(&mut [splat(1.)]);
+    util.reset_state();
+    util.control(|ctl| ctl.reset());
+    assert_eq!(util.gain.value(), 0.5);
+    let frames = &mut [splat::<Mo>(1.); 2];
+    util.proc(frames);
+    assert_eq!(frames, &[splat(0.), splat(0.)]);
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use common::assert_near;
+use sn_dsp::{
+    filter::{
+        svf,
+        BiquadCoefs,
+        Response,
+        Svf,
+        SvfMode,
+    },
+    frame::{
+        splat,
+        Mo,
+    },
+    node::Node,
+    num::{
+        Fp,
+        Real,
+    },
+    Control,
+    Reset,
+};
+
+const MINUS_3DB: Fp = -3.010_299_956_639_812;
+
+fn impulse(
+    node: &mut impl Node<Frame = Mo>,
+    len: usize,
+) -> Vec<Fp> {
+    let mut frames = vec![splat(0.); len];
+    frames[0] = splat(1.);
+    node.proc(&mut frames);
+    frames.iter().map(|f| f[0]).collect()
+}
+
+#[test]
+fn check_svf_response_01() {
+    let q = 1. / Fp::SQRT_2;
+    let lp = svf::<Mo>(SvfMode::Lowpass, 0.1, q);
+    assert_near(lp.gain_db(0.1), MINUS_3DB, 1e-9);
+    assert_near(lp.gain_db(0.), 0., 1e-9);
+    // same bilinear transform as the Butterworth biquad
+    let butter = BiquadCoefs::butter_lowpass(0.1);
+    for freq in [0.01, 0.05, 0.2, 0.3] {
+        assert_near(lp.gain_db(freq), butter.gain_db(freq), 1e-9);
+    }
+
+    let hp = svf::<Mo>(SvfMode::Hipass, 0.1, q);
+    assert_near(hp.gain_db(0.1), MINUS_3DB, 1e-9);
+    assert_near(hp.gain_db(0.5), 0., 1e-9);
+
+    let bp = svf::<Mo>(SvfMode::Bandpass, 0.1, 2.);
+    assert_near(bp.gain_db(0.1), 20. * Fp::log10(2.), 1e-9);
+    let notch = svf::<Mo>(SvfMode::Notch, 0.1, 2.);
+    assert!(notch.gain_db(0.1) < -100.);
+    let peak = svf::<Mo>(SvfMode::Peak, 0.1, 2.);
+    assert_near(peak.gain_db(0.1), 20. * Fp::log10(4.), 1e-9);
+}
+
+#[test]
+fn check_svf_impulse_01() {
+    for mode in [
+        SvfMode::Lowpass,
+        SvfMode::Hipass,
+        SvfMode::Bandpass,
+        SvfMode::Notch,
+        SvfMode::Peak,
+    ] {
+        let mut filter = Svf::<Mo>::new(mode, 0.13, 1.3);
+        let ir = impulse(&mut filter.clone(), 4096);
+
+        // compare the spectrum of the impulse response with the response
+        for freq in [0.02, 0.13, 0.31] {
+            let mut re = 0.;
+            let mut im = 0.;
+            for (n, x) in ir.iter().enumerate() {
+                let w = -Fp::TAU * freq * n as Fp;
+                re += x * w.cos();
+                im += x * w.sin();
+            }
+            let h = filter.response(freq);
+            assert_near(re, h.re, 1e-6);
+            assert_near(im, h.im, 1e-6);
+        }
+        filter.reset_state();
+    }
+}
+
+#[test]
+fn check_svf_outputs_01() {
+    let mut filter = Svf::<Mo>::new(SvfMode::Lowpass, 0.05, 0.9);
+    for n in 0..100 {
+        let x = splat((n as Fp * 0.3).sin());
+        let out = filter.tick_all(x);
+        assert_near(out.notch[0], out.lowpass[0] + out.hipass[0], 1e-12);
+        assert_near(out.peak[0], out.lowpass[0] - out.hipass[0], 1e-12);
+        assert_near(
+            out.lowpass[0] + out.hipass[0] + out.bandpass[0] / 0.9,
+            x[0],
+            1e-12,
+        );
+    }
+}
+
+#[test]
+fn check_svf_modulation_01() {
+    // sweep the cutoff at audio rate with high resonance
+    let mut filter = Svf::<Mo>::new(SvfMode::Lowpass, 0.1, 20.);
+    let len = 48_000;
+    let cutoff: Vec<Fp> = (0..len)
+        .map(|n| 0.2 + 0.19 * (n as Fp * 0.37).sin())
+        .collect();
+    let mut frames: Vec<Mo> =
+        (0..len).map(|n| splat((n as Fp * 0.05).sin())).collect();
+    filter.proc_cutoff(&mut frames, &cutoff);
+    assert!(frames.iter().all(|f| f[0].is_finite() && f[0].abs() < 100.));
+    assert_near(filter.cutoff(), cutoff[len - 1], 1e-12);
+}
+
+#[test]
+fn check_svf_ctl_01() {
+    let mut filter = svf::<Mo>(SvfMode::Lowpass, 0.1, 1.);
+    impulse(&mut filter, 4);
+    filter.control(|ctl| {
+        ctl.set_cutoff(0.2);
+        ctl.set_q(3.);
+        *ctl.mode = SvfMode::Bandpass;
+    });
+    assert_near(filter.cutoff(), 0.2, 1e-12);
+    assert_near(filter.q(), 3., 1e-12);
+    assert_eq!(filter.mode, SvfMode::Bandpass);
+
+    let mut fresh = filter.clone();
+    fresh.reset_state();
+    filter.control(|ctl| ctl.reset());
+    assert_eq!(impulse(&mut filter, 16), impulse(&mut fresh, 16));
+}
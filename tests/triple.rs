@@ -0,0 +1,56 @@
+use std::thread;
+
+use sn_dsp::sync::triple;
+
+#[test]
+fn check_triple_01() {
+    let (_writer, mut reader) = triple(|| 7);
+    assert!(!reader.has_update());
+    assert_eq!(*reader.read(), 7);
+}
+
+#[test]
+fn check_triple_publish_01() {
+    let (mut writer, mut reader) = triple(|| 0);
+    *writer.write() = 1;
+    writer.publish();
+    *writer.write() = 2;
+    writer.publish();
+    assert!(reader.has_update());
+    assert_eq!(*reader.read(), 2);
+    assert!(!reader.has_update());
+    // no update: the same value is read again
+    assert_eq!(*reader.read(), 2);
+}
+
+#[test]
+fn check_triple_unpublished_01() {
+    let (mut writer, mut reader) = triple(|| 0);
+    *writer.write() = 1;
+    writer.publish();
+    assert_eq!(*reader.read(), 1);
+    *writer.write() = 2;
+    assert!(!reader.has_update());
+    assert_eq!(*reader.read(), 1);
+}
+
+#[test]
+fn check_triple_threads_01() {
+    const N: u64 = 100_000;
+    let (mut writer, mut reader) = triple(|| [0_u64; 16]);
+    let handle = thread::spawn(move || {
+        for i in 1..=N {
+            writer.write().fill(i);
+            writer.publish();
+        }
+    });
+    let mut last = 0;
+    while last < N {
+        let value = reader.read();
+        // never torn, never older than the last read
+        assert!(value.iter().all(|x| *x == value[0]));
+        assert!(value[0] >= last);
+        last = value[0];
+    }
+    handle.join().unwrap();
+}